@@ -0,0 +1,172 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+// Signed, memory allocated before counting was enabled may be freed while it is.
+static LIVE: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+/// The system allocator, counting allocations once [`enable_allocation_counting`] is called.
+///
+/// A day binary opts in by declaring it:
+///
+/// ```
+/// #[global_allocator]
+/// static ALLOCATOR: aoc_common::CountingAllocator = aoc_common::CountingAllocator;
+///
+/// fn main() {
+///     assert!(aoc_common::enable_allocation_counting());
+/// }
+/// ```
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn on_alloc(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+
+        if ENABLED.load(Ordering::Relaxed) {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size as u64, Ordering::Relaxed);
+            let live = LIVE.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+    }
+
+    fn on_dealloc(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            LIVE.fetch_sub(size as i64, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            CountingAllocator::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            CountingAllocator::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CountingAllocator::on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CountingAllocator::on_dealloc(layout.size());
+            CountingAllocator::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// What a stretch of code allocated: how often, how much in total, and the most it held at
+/// once on top of what was already live when it started.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: u64,
+    pub bytes: u64,
+    pub peak_bytes: u64,
+}
+
+/// Starts counting, or returns false when the binary does not use [`CountingAllocator`].
+pub fn enable_allocation_counting() -> bool {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    ENABLED.store(true, Ordering::Relaxed);
+    true
+}
+
+pub fn is_counting_allocations() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and returns what it allocated, `None` while counting is off. The counters are
+/// global, so allocations made by other threads in the meantime are included.
+pub fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !is_counting_allocations() {
+        return (f(), None);
+    }
+
+    let count = COUNT.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let value = f();
+
+    let allocations = Allocations {
+        count: COUNT.load(Ordering::Relaxed) - count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: (PEAK.load(Ordering::Relaxed) - live).max(0) as u64,
+    };
+
+    (value, Some(allocations))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn counting() {
+        assert!(enable_allocation_counting());
+
+        let (length, allocations) = count_allocations(|| {
+            let mut values: Vec<u8> = Vec::with_capacity(1 << 20);
+            values.push(1);
+            drop(Vec::<u8>::with_capacity(1 << 20));
+            values.len()
+        });
+
+        // Other tests run alongside and share the counters, so only lower bounds hold.
+        let allocations = allocations.unwrap();
+        assert_eq!(length, 1);
+        assert!(allocations.count >= 2);
+        assert!(allocations.bytes >= 2 << 20);
+        assert!(allocations.peak_bytes >= 1 << 20);
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+    }
+}
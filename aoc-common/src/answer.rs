@@ -0,0 +1,124 @@
+use std::fmt;
+
+/// Why a part has no answer when it does not fit in the integers it is worked out in.
+pub const OVERFLOW_REASON: &str = "does not fit in 64 bits";
+
+/// Result of a single puzzle part.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+    /// No answer for this input, and why, such as a result that overflowed or a part not
+    /// solved yet. Records report it as a failed part rather than as an answer.
+    Unsolved(String),
+}
+
+impl Answer {
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            Answer::Signed(_) => "signed",
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Text(_) => "text",
+            Answer::Unsolved(_) => "unsolved",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+            Answer::Unsolved(reason) => write!(f, "unsolved: {reason}"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Signed(value as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+/// Values past an `i64` keep their digits as [`Answer::Text`], so they still print and compare.
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        i64::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Signed)
+    }
+}
+
+/// Values past a `u64` keep their digits as [`Answer::Text`], so they still print and compare.
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        u64::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Unsigned)
+    }
+}
+
+/// A part worked out with checked arithmetic, which is `None` when it overflowed.
+impl From<Option<u64>> for Answer {
+    fn from(value: Option<u64>) -> Self {
+        value.map_or_else(|| Answer::Unsolved(OVERFLOW_REASON.to_string()), Answer::Unsigned)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-12).to_string(), "-12");
+        assert_eq!(Answer::from(579439039_u64).to_string(), "579439039");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Answer::from(2512_i32), Answer::Signed(2512));
+        assert_eq!(Answer::from(25651_u32), Answer::Unsigned(25651));
+        assert_eq!(Answer::from(30_usize), Answer::Unsigned(30));
+        assert_eq!(Answer::from(-7_i128), Answer::Signed(-7));
+        assert_eq!(Answer::from(7_u128), Answer::Unsigned(7));
+        assert_eq!(Answer::from(u128::from(u64::MAX) + 1), Answer::Text("18446744073709551616".to_string()));
+        assert_eq!(Answer::from(Some(7_u64)), Answer::Unsigned(7));
+        assert_eq!(Answer::from(None::<u64>).to_string(), "unsolved: does not fit in 64 bits");
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{InputResolver, Part, Solution};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+pub const PUZZLE_INPUT: &str = "puzzle";
+pub const ANSWER_TESTS_FILE: &str = "answer_tests.rs";
+
+#[derive(Debug)]
+pub struct AnswersError {
+    pub path: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "{}:{}: {}", self.path.display(), self.line, self.message)
+        } else {
+            write!(f, "{}: {}", self.path.display(), self.message)
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// Expected answers for one day and one profile's input.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Expectation {
+    pub answers: BTreeMap<Part, String>,
+    pub slow: Vec<Part>,
+}
+
+/// Known answers, read from `answers.toml`.
+///
+/// Each table is named `[dayNN.<profile>]`, where `<profile>` is `puzzle` for the
+/// day's default input, `inputs/dayNN.txt`, and any other name for the input in
+/// `inputs/<profile>/dayNN.txt`. It holds `part_one`/`part_two` values plus an
+/// optional `slow` list of parts too expensive to run by default.
+#[derive(Debug, Default)]
+pub struct Answers {
+    expectations: BTreeMap<(u8, String), Expectation>,
+}
+
+impl Answers {
+    pub fn parse(text: &str, path: &Path) -> Result<Self, AnswersError> {
+        let error = |line: usize, message: String| AnswersError {
+            path: path.to_path_buf(),
+            line,
+            message,
+        };
+
+        let mut answers = Answers::default();
+        let mut current: Option<(u8, String)> = None;
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let number = index + 1;
+            let line = strip_comment(raw_line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| error(number, format!("unterminated table header: {line}")))?;
+
+                let key = parse_table_name(header).ok_or_else(|| {
+                    error(number, format!("expected a table named [dayNN.<profile>], found [{header}]"))
+                })?;
+
+                answers.expectations.entry(key.clone()).or_default();
+                current = Some(key);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(number, format!("expected key = value, found {line}")))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let table = current
+                .as_ref()
+                .ok_or_else(|| error(number, format!("{key} is outside of a [dayNN.<profile>] table")))?;
+            let expectation = answers.expectations.get_mut(table).unwrap();
+
+            match key {
+                "slow" => {
+                    expectation.slow = parse_array(value)
+                        .and_then(|parts| parts.iter().map(|part| parse_part_key(part)).collect())
+                        .ok_or_else(|| error(number, format!("slow must be a list of parts, found {value}")))?;
+                }
+                _ => {
+                    let part = parse_part_key(key).ok_or_else(|| error(number, format!("unknown key {key}")))?;
+                    let value = parse_value(value).ok_or_else(|| error(number, format!("invalid value {value}")))?;
+
+                    expectation.answers.insert(part, value);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let text = fs::read_to_string(path).map_err(|io_error| AnswersError {
+            path: path.to_path_buf(),
+            line: 0,
+            message: io_error.to_string(),
+        })?;
+
+        Answers::parse(&text, path)
+    }
+
+    /// Loads `answers.toml` from the current directory or its nearest ancestor that has one.
+    pub fn find() -> Result<Self, AnswersError> {
+        let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+        let path = current_dir
+            .ancestors()
+            .map(|directory| directory.join(ANSWERS_FILE))
+            .find(|path| path.is_file())
+            .ok_or_else(|| AnswersError {
+                path: current_dir.join(ANSWERS_FILE),
+                line: 0,
+                message: "not found here or in any parent directory".to_string(),
+            })?;
+
+        Answers::load(&path)
+    }
+
+    pub fn get(&self, day: u8, profile: &str) -> Option<&Expectation> {
+        self.expectations.get(&(day, profile.to_string()))
+    }
+
+    pub fn get_answer(&self, day: u8, profile: &str, part: Part) -> Option<&str> {
+        self.get(day, profile)?.answers.get(&part).map(|answer| answer.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &str, &Expectation)> {
+        self.expectations
+            .iter()
+            .map(|((day, profile), expectation)| (*day, profile.as_str(), expectation))
+    }
+}
+
+pub fn get_part_key(part: Part) -> &'static str {
+    match part {
+        Part::One => "part_one",
+        Part::Two => "part_two",
+    }
+}
+
+fn parse_part_key(key: &str) -> Option<Part> {
+    Part::ALL.into_iter().find(|part| get_part_key(*part) == key)
+}
+
+fn parse_table_name(header: &str) -> Option<(u8, String)> {
+    let (day, profile) = header.trim().split_once('.')?;
+    let day = day.strip_prefix("day")?.parse::<u8>().ok()?;
+    let profile = profile.trim();
+
+    if !is_profile_name(profile) {
+        return None;
+    }
+
+    Some((day, profile.to_string()))
+}
+
+/// Profile names double as directory names under `inputs`, so only a safe subset is allowed.
+pub fn is_profile_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '-')
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+
+    for (index, char) in line.char_indices() {
+        match char {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(text) = value.strip_prefix('"') {
+        let text = text.strip_suffix('"')?;
+
+        if text.contains('"') || text.contains('\\') {
+            return None;
+        }
+
+        return Some(text.to_string());
+    }
+
+    let digits = value.replace('_', "");
+    let unsigned = digits.strip_prefix(['-', '+']).unwrap_or(&digits);
+
+    if unsigned.is_empty() || !unsigned.chars().all(|char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(digits.trim_start_matches('+').to_string())
+}
+
+fn parse_array(value: &str) -> Option<Vec<String>> {
+    let inner = value.strip_prefix('[')?.strip_suffix(']')?.trim();
+
+    if inner.is_empty() {
+        return Some(vec![]);
+    }
+
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_value)
+        .collect()
+}
+
+/// Checks a day's answer on `profile`'s input against `answers.toml`, panicking on any difference.
+///
+/// The build script ignores the check when the input is missing, or only encrypted without a
+/// key, see [`get_answer_tests`], so failing to read it here is a failure like any other.
+pub fn assert_answer<S: Solution>(profile: &str, part: Part) {
+    let answers = Answers::find().unwrap_or_else(|error| panic!("{error}"));
+
+    let expected = answers
+        .get_answer(S::DAY, profile, part)
+        .unwrap_or_else(|| panic!("no expected answer for [day{:02}.{profile}] {} in {ANSWERS_FILE}", S::DAY, get_part_key(part)));
+
+    let resolver = InputResolver::from_env().with_profile(profile);
+
+    let input = resolver.read(S::DAY, None).unwrap_or_else(|error| panic!("{error}"));
+    let parsed = S::parse(&input).unwrap_or_else(|error| panic!("{}", error.locate(&input)));
+    let actual = S::solve(&parsed, part).to_string();
+
+    assert_eq!(actual, expected, "day {} profile {profile} part {part} does not match {ANSWERS_FILE}", S::DAY);
+}
+
+/// The profiles with answer tests for `day`: the puzzle input, then every profile `answers`
+/// has a table for.
+pub fn get_answer_profiles(answers: &Answers, day: u8) -> Vec<&str> {
+    let others = answers.iter().filter(|(other, profile, _)| *other == day && *profile != PUZZLE_INPUT);
+
+    std::iter::once(PUZZLE_INPUT).chain(others.map(|(_, profile, _)| profile)).collect()
+}
+
+/// Generates the [`answer_tests!`](crate::answer_tests) calls for a day, the puzzle input's at
+/// the top and every other profile's in a `profile_<name>` module; `package` is the crate
+/// name, such as `day05`.
+///
+/// A part is ignored when `answers` lists it as slow or has no answer for it yet, or when
+/// `resolver` finds no input for its profile, or only an encrypted one and no key.
+/// `answers.toml` stays the only place a part is marked slow.
+pub fn get_answer_tests(answers: &Answers, package: &str, resolver: &InputResolver) -> Result<String, String> {
+    let day = package
+        .strip_prefix("day")
+        .and_then(|day| day.parse::<u8>().ok())
+        .ok_or_else(|| format!("{package} is not named dayNN"))?;
+
+    let mut code = String::new();
+
+    for profile in get_answer_profiles(answers, day) {
+        let expectation = answers.get(day, profile).cloned().unwrap_or_default();
+        let unavailable = resolver.clone().with_profile(profile).get_unavailable_reason(day);
+
+        let attributes = |part: Part| {
+            if !expectation.answers.contains_key(&part) {
+                format!("#[ignore = \"no answer recorded in {ANSWERS_FILE} yet\"]")
+            } else if expectation.slow.contains(&part) {
+                format!("#[ignore = \"listed as slow in {ANSWERS_FILE}, run with --ignored --release\"]")
+            } else if let Some(reason) = &unavailable {
+                format!("#[ignore = {reason:?}]")
+            } else {
+                String::new()
+            }
+        };
+
+        let tests = format!(
+            "aoc_common::answer_tests!(\n    crate::Day{day:02},\n    profile: {profile:?},\n    part_one: [{}],\n    part_two: [{}]\n);\n",
+            attributes(Part::One),
+            attributes(Part::Two),
+        );
+
+        if profile == PUZZLE_INPUT {
+            code.push_str(&tests);
+        } else {
+            code.push_str(&format!("\nmod profile_{} {{\n{tests}}}\n", profile.replace('-', "_")));
+        }
+    }
+
+    Ok(code)
+}
+
+/// Generates one test per part checking a profile's answers against `answers.toml`, the
+/// puzzle input's when no profile is given.
+///
+/// Extra attributes can be given per part; a day's build script passes `#[ignore]` for the
+/// parts that are slow or cannot be checked, see [`get_answer_tests`].
+#[macro_export]
+macro_rules! answer_tests {
+    ($solution:ty) => {
+        $crate::answer_tests!($solution, profile: $crate::PUZZLE_INPUT, part_one: [], part_two: []);
+    };
+    ($solution:ty, profile: $profile:expr, part_one: [$(#[$one:meta])*], part_two: [$(#[$two:meta])*]) => {
+        #[test]
+        $(#[$one])*
+        fn answer_part_one() {
+            $crate::assert_answer::<$solution>($profile, $crate::Part::One);
+        }
+
+        #[test]
+        $(#[$two])*
+        fn answer_part_two() {
+            $crate::assert_answer::<$solution>($profile, $crate::Part::Two);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "# known answers
+[day03.puzzle]
+part_one = 536_202
+part_two = 78272573 # gears
+
+[day05.puzzle]
+part_one = 579439039
+part_two = 7873084
+slow = [\"part_two\"]
+
+[day07.alice]
+part_two = \"abc#def\"
+";
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(TEXT, Path::new(ANSWERS_FILE)).unwrap();
+
+        assert_eq!(answers.get_answer(3, PUZZLE_INPUT, Part::One), Some("536202"));
+        assert_eq!(answers.get_answer(3, PUZZLE_INPUT, Part::Two), Some("78272573"));
+        assert_eq!(answers.get(5, PUZZLE_INPUT).unwrap().slow, vec![Part::Two]);
+        assert_eq!(answers.get_answer(7, "alice", Part::One), None);
+        assert_eq!(answers.get_answer(7, "alice", Part::Two), Some("abc#def"));
+        assert_eq!(answers.iter().count(), 3);
+    }
+
+    #[test]
+    fn errors_point_at_line() {
+        let error = Answers::parse("[day01.puzzle]\npart_three = 1\n", Path::new("answers.toml")).unwrap_err();
+        assert_eq!(error.to_string(), "answers.toml:2: unknown key part_three");
+
+        let error = Answers::parse("part_one = 1\n", Path::new("answers.toml")).unwrap_err();
+        assert_eq!(error.line, 1);
+
+        let error = Answers::parse("[puzzle]\n", Path::new("answers.toml")).unwrap_err();
+        assert!(error.message.contains("dayNN.<profile>"));
+
+        let error = Answers::parse("[day01.puzzle]\npart_one = 1.5\n", Path::new("answers.toml")).unwrap_err();
+        assert!(error.message.contains("invalid value"));
+    }
+
+    #[test]
+    fn generated_tests() {
+        let answers = Answers::parse(TEXT, Path::new(ANSWERS_FILE)).unwrap();
+        let root = env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("alice")).unwrap();
+        for file in ["day03.txt", "day05.txt", "alice/day07.txt"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let resolver = InputResolver::new(Some(root.clone()), root.clone());
+
+        let code = get_answer_tests(&answers, "day05", &resolver).unwrap();
+        assert!(code.contains("crate::Day05,\n    profile: \"puzzle\",\n    part_one: [],\n"));
+        assert!(code.contains("part_two: [#[ignore = \"listed as slow in answers.toml, run with --ignored --release\"]]"));
+
+        let code = get_answer_tests(&answers, "day03", &resolver).unwrap();
+        assert!(code.contains("crate::Day03,\n    profile: \"puzzle\",\n    part_one: [],\n    part_two: []\n"));
+
+        // day07 has answers for another profile alone, and no puzzle input to check them on.
+        let code = get_answer_tests(&answers, "day07", &resolver).unwrap();
+        assert!(code.starts_with("aoc_common::answer_tests!(\n    crate::Day07,\n    profile: \"puzzle\",\n    part_one: [#[ignore = \"no answer recorded in answers.toml yet\"]],"));
+        assert!(code.contains("\nmod profile_alice {\naoc_common::answer_tests!(\n    crate::Day07,\n    profile: \"alice\",\n"));
+        assert!(code.ends_with("    part_two: []\n);\n}\n"));
+
+        // Without an input, the parts that could be checked are ignored too.
+        let empty = InputResolver::new(Some(root.join("alice")), root.join("alice"));
+        let code = get_answer_tests(&answers, "day05", &empty).unwrap();
+        assert!(code.contains("part_one: [#[ignore = \"no input found for day 5, looked in:\"]],"));
+        assert!(code.contains("part_two: [#[ignore = \"listed as slow in answers.toml, run with --ignored --release\"]]"));
+
+        assert!(get_answer_tests(&answers, "aoc", &resolver).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use std::str::FromStr;
+
+/// Minimal command line parser.
+///
+/// Flags and options are taken out first, whatever remains is positional.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new<I: IntoIterator<Item = String>>(args: I) -> Self {
+        Args {
+            args: args.into_iter().collect(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Args::new(std::env::args().skip(1))
+    }
+
+    pub fn subcommand(&mut self) -> Option<String> {
+        match self.args.first() {
+            Some(arg) if !arg.starts_with('-') => Some(self.args.remove(0)),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        if let Some(index) = self.args.iter().position(|arg| arg == name) {
+            self.args.remove(index);
+            return true;
+        }
+
+        false
+    }
+
+    pub fn option(&mut self, name: &str) -> Result<Option<String>, String> {
+        let prefix = format!("{name}=");
+
+        for index in 0..self.args.len() {
+            if let Some(value) = self.args[index].strip_prefix(&prefix) {
+                let value = value.to_string();
+                self.args.remove(index);
+                return Ok(Some(value));
+            }
+
+            if self.args[index] == name {
+                if index + 1 >= self.args.len() {
+                    return Err(format!("missing value for {name}"));
+                }
+
+                let value = self.args.remove(index + 1);
+                self.args.remove(index);
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn parsed_option<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        match self.option(name)? {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid value for {name}: {value}")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn positional(&mut self) -> Option<String> {
+        let index = self.args.iter().position(|arg| arg == "-" || !arg.starts_with('-'))?;
+
+        Some(self.args.remove(index))
+    }
+
+    pub fn parsed_positional<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        match self.positional() {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid {name}: {value}")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn finish(self) -> Result<(), String> {
+        match self.args.first() {
+            Some(arg) => Err(format!("unexpected argument: {arg}")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(text: &str) -> Args {
+        Args::new(text.split_whitespace().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn flags_options_and_positionals() {
+        let mut args = args("run 5 --part 2 --all");
+
+        assert_eq!(args.subcommand().as_deref(), Some("run"));
+        assert!(args.flag("--all"));
+        assert!(!args.flag("--verbose"));
+        assert_eq!(args.parsed_option::<u8>("--part"), Ok(Some(2)));
+        assert_eq!(args.parsed_positional::<u8>("day"), Ok(Some(5)));
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn option_with_equals() {
+        let mut args = args("--format=json -");
+
+        assert_eq!(args.subcommand(), None);
+        assert_eq!(args.option("--format"), Ok(Some("json".to_string())));
+        assert_eq!(args.positional().as_deref(), Some("-"));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(args("--part").option("--part").is_err());
+        assert!(args("--part x").parsed_option::<u8>("--part").is_err());
+        assert!(args("--unknown").finish().is_err());
+    }
+}
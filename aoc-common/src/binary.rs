@@ -0,0 +1,109 @@
+use std::process::ExitCode;
+use std::time::Duration;
+
+use crate::{
+    enable_allocation_counting, format_records, init_tracing, get_records, read_source, watch, Args, Format, InputResolver, Part,
+    Puzzle, DEFAULT_WATCH_INTERVAL,
+};
+
+const USAGE: &str =
+    "[-v | -vv] [--profile <name>] [--format <text|json|csv>] [--memory] [--watch [--interval <seconds>]] [input path | -]";
+
+struct Options {
+    profile: Option<String>,
+    format: Format,
+    memory: bool,
+    watch_interval: Option<Duration>,
+    explicit: Option<String>,
+}
+
+/// Entry point shared by the day binaries:
+/// `dayNN [-v | -vv] [--profile <name>] [--format <text|json|csv>] [--memory] [--watch [--interval <seconds>]] [input path | -]`.
+///
+/// `-v` traces parsing and each part, `-vv` adds the solvers' progress; `$AOC_LOG` does the
+/// same with `info` or `debug`. `--memory` needs the binary to declare [`crate::CountingAllocator`] as its global allocator.
+pub fn day_main(puzzle: &dyn Puzzle) -> ExitCode {
+    let options = match get_options(Args::from_env()) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {error}\nusage: day{:02} {USAGE}", puzzle.get_day());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if options.memory && !enable_allocation_counting() {
+        eprintln!("error: day{:02} cannot count allocations, it does not use aoc_common::CountingAllocator", puzzle.get_day());
+        return ExitCode::FAILURE;
+    }
+
+    let resolver = match options.profile {
+        Some(profile) => InputResolver::from_env().with_profile(&profile),
+        None => InputResolver::from_env(),
+    };
+
+    if let Some(interval) = options.watch_interval {
+        watch(puzzle, &resolver, options.explicit.as_deref(), interval);
+    }
+
+    let source = match resolver.resolve(puzzle.get_day(), options.explicit.as_deref()) {
+        Ok(source) => source,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input = match read_source(&source) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let records = get_records(puzzle, &input, &Part::ALL, &source);
+    print!("{}", format_records(&records, options.format));
+
+    // A parse error fails every part with the same message, report it once.
+    let mut errors: Vec<&str> = records.iter().filter_map(|record| record.error.as_deref()).collect();
+    errors.dedup();
+
+    for error in &errors {
+        eprintln!("error: {error}");
+    }
+
+    if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn get_options(mut args: Args) -> Result<Options, String> {
+    init_tracing(&mut args)?;
+    let profile = args.option("--profile")?;
+    let format = args.parsed_option::<Format>("--format")?;
+    let memory = args.flag("--memory");
+    let watch = args.flag("--watch");
+    let interval = args.parsed_option::<f64>("--interval")?;
+    let explicit = args.positional();
+    args.finish()?;
+
+    let watch_interval = match (watch, interval) {
+        (false, None) => None,
+        (false, Some(_)) => return Err("--interval only applies to --watch".to_string()),
+        (true, _) if explicit.as_deref() == Some("-") => return Err("cannot watch stdin".to_string()),
+        (true, _) if format.is_some() || memory => return Err("--format and --memory do not apply to --watch".to_string()),
+        (true, None) => Some(DEFAULT_WATCH_INTERVAL),
+        (true, Some(seconds)) if seconds.is_finite() && seconds > 0.0 => Some(Duration::from_secs_f64(seconds)),
+        (true, Some(seconds)) => return Err(format!("invalid watch interval: {seconds}")),
+    };
+
+    Ok(Options {
+        profile,
+        format: format.unwrap_or_default(),
+        memory,
+        watch_interval,
+        explicit,
+    })
+}
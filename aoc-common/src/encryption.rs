@@ -0,0 +1,224 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::crypto::{chacha20, constant_time_eq, from_hex, hmac_sha256, to_hex};
+
+pub const KEY_VARIABLE: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE_VARIABLE: &str = "AOC_INPUT_KEY_FILE";
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+const NONCE_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 32;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    MissingKey(Option<PathBuf>),
+    InvalidKey(String),
+    KeyFile(PathBuf, io::Error),
+    NotEncrypted,
+    WrongKey,
+}
+
+impl fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionError::MissingKey(Some(key_file)) => write!(
+                f,
+                "no input key, set {KEY_VARIABLE} or write one to {} (`aoc encrypt --generate-key` creates it)",
+                key_file.display()
+            ),
+            EncryptionError::MissingKey(None) => write!(f, "no input key, set {KEY_VARIABLE} or {KEY_FILE_VARIABLE}"),
+            EncryptionError::InvalidKey(source) => write!(f, "{source}: an input key is 64 hexadecimal characters"),
+            EncryptionError::KeyFile(path, error) => write!(f, "{}: {error}", path.display()),
+            EncryptionError::NotEncrypted => write!(f, "not an encrypted input"),
+            EncryptionError::WrongKey => write!(f, "cannot decrypt, the key is wrong or the file was modified"),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+/// The symmetric key puzzle inputs are encrypted with, kept outside of the repository.
+///
+/// Inputs are encrypted with ChaCha20 and authenticated with HMAC-SHA256, using separate
+/// keys derived from this one. The nonce is derived from the text itself, so encrypting
+/// the same input twice gives the same file and re-encrypting does not churn the history.
+#[derive(Clone)]
+pub struct Key([u8; 32]);
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+impl Key {
+    /// Parses 64 hexadecimal characters; `source` names where they came from in errors.
+    pub fn parse(text: &str, source: &str) -> Result<Self, EncryptionError> {
+        from_hex(text.trim())
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Key)
+            .ok_or_else(|| EncryptionError::InvalidKey(source.to_string()))
+    }
+
+    /// Creates a random key from the operating system's random number generator.
+    pub fn generate() -> io::Result<Self> {
+        let mut bytes = [0; 32];
+        File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+
+        Ok(Key(bytes))
+    }
+
+    /// Reads the key from `$AOC_INPUT_KEY`, else from the key file.
+    pub fn from_env() -> Result<Self, EncryptionError> {
+        if let Some(text) = env::var(KEY_VARIABLE).ok().filter(|text| !text.is_empty()) {
+            return Key::parse(&text, KEY_VARIABLE);
+        }
+
+        let Some(key_file) = get_key_file() else {
+            return Err(EncryptionError::MissingKey(None));
+        };
+
+        match fs::read_to_string(&key_file) {
+            Ok(text) => Key::parse(&text, &key_file.display().to_string()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Err(EncryptionError::MissingKey(Some(key_file))),
+            Err(error) => Err(EncryptionError::KeyFile(key_file, error)),
+        }
+    }
+
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    /// Encrypts `text` into the `.enc` format: magic, nonce, ciphertext and tag.
+    ///
+    /// The nonce is an HMAC of the text rather than random, as in SIV modes. Two different texts
+    /// still never share a nonce under one key, but identical texts give identical files, so
+    /// anyone who can read the repository can tell when an input is unchanged or when two
+    /// inputs are the same, though not what they contain. That is the price of re-encrypting
+    /// without touching the history.
+    pub fn encrypt(&self, text: &[u8]) -> Vec<u8> {
+        let (cipher_key, mac_key) = self.get_subkeys();
+
+        let mut nonce = [0; NONCE_LENGTH];
+        nonce.copy_from_slice(&hmac_sha256(&mac_key, &[b"nonce", text])[..NONCE_LENGTH]);
+
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&nonce);
+
+        let start = data.len();
+        data.extend_from_slice(text);
+        chacha20(&cipher_key, &nonce, 0, &mut data[start..]);
+
+        let tag = hmac_sha256(&mac_key, &[&data]);
+        data.extend_from_slice(&tag);
+
+        data
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let (cipher_key, mac_key) = self.get_subkeys();
+
+        let body = data.strip_prefix(MAGIC).ok_or(EncryptionError::NotEncrypted)?;
+        if body.len() < NONCE_LENGTH + TAG_LENGTH {
+            return Err(EncryptionError::NotEncrypted);
+        }
+
+        let (authenticated, tag) = data.split_at(data.len() - TAG_LENGTH);
+        if !constant_time_eq(&hmac_sha256(&mac_key, &[authenticated]), tag) {
+            return Err(EncryptionError::WrongKey);
+        }
+
+        let mut nonce = [0; NONCE_LENGTH];
+        nonce.copy_from_slice(&body[..NONCE_LENGTH]);
+
+        let mut text = authenticated[MAGIC.len() + NONCE_LENGTH..].to_vec();
+        chacha20(&cipher_key, &nonce, 0, &mut text);
+
+        Ok(text)
+    }
+
+    fn get_subkeys(&self) -> ([u8; 32], [u8; 32]) {
+        (hmac_sha256(&self.0, &[b"cipher"]), hmac_sha256(&self.0, &[b"mac"]))
+    }
+}
+
+/// `$AOC_INPUT_KEY_FILE`, else `~/.config/aoc-2023/input.key`.
+pub fn get_key_file() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(KEY_FILE_VARIABLE).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(|home| PathBuf::from(home).join(".config").join("aoc-2023").join("input.key"))
+}
+
+/// `dayNN.txt` becomes `dayNN.txt.enc`.
+pub fn get_encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{ENCRYPTED_EXTENSION}"));
+
+    PathBuf::from(name)
+}
+
+pub fn is_encrypted_path(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == ENCRYPTED_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn round_trip() {
+        let key = Key::parse(KEY, "test").unwrap();
+        let text = b"Time:      7  15   30\nDistance:  9  40  200\n";
+
+        let data = key.encrypt(text);
+
+        assert!(data.starts_with(MAGIC));
+        assert!(!data.windows(5).any(|window| window == b"Time:"));
+        assert_eq!(key.encrypt(text), data);
+        assert_eq!(key.decrypt(&data).unwrap(), text);
+        assert_ne!(key.encrypt(b"other"), key.encrypt(b"Other"));
+    }
+
+    #[test]
+    fn rejects_wrong_key_and_tampering() {
+        let key = Key::parse(KEY, "test").unwrap();
+        let data = key.encrypt(b"seeds: 79 14 55 13");
+
+        let other = Key::parse(&KEY.replace("00", "ff"), "test").unwrap();
+        assert!(matches!(other.decrypt(&data), Err(EncryptionError::WrongKey)));
+
+        let mut tampered = data.clone();
+        tampered[MAGIC.len() + NONCE_LENGTH] ^= 1;
+        assert!(matches!(key.decrypt(&tampered), Err(EncryptionError::WrongKey)));
+
+        assert!(matches!(key.decrypt(b"seeds: 79 14 55 13"), Err(EncryptionError::NotEncrypted)));
+    }
+
+    #[test]
+    fn parse_key() {
+        assert_eq!(Key::parse(&format!("{KEY}\n"), "test").unwrap().to_hex(), KEY);
+        assert!(Key::parse("abcd", "test").is_err());
+        assert_eq!(format!("{:?}", Key::parse(KEY, "test").unwrap()), "Key(..)");
+    }
+
+    #[test]
+    fn encrypted_paths() {
+        let path = get_encrypted_path(Path::new("inputs/day05.txt"));
+
+        assert_eq!(path, PathBuf::from("inputs/day05.txt.enc"));
+        assert!(is_encrypted_path(&path));
+        assert!(!is_encrypted_path(Path::new("inputs/day05.txt")));
+    }
+}
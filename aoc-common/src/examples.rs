@@ -0,0 +1,190 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    get_answer_profiles, get_answer_tests, get_fuzz_tests, get_key_file, get_part_key, Answers, InputResolver, Part, Solution,
+    ANSWERS_FILE, ANSWER_TESTS_FILE, FUZZ_DIRECTORY, FUZZ_TESTS_FILE, INPUT_DIR_VARIABLE, KEY_FILE_VARIABLE, KEY_VARIABLE,
+};
+
+pub const EXAMPLES_DIRECTORY: &str = "examples";
+pub const EXAMPLES_FILE: &str = "expected.toml";
+pub const EXAMPLE_TESTS_FILE: &str = "example_tests.rs";
+
+/// Build script entry point for a day crate.
+///
+/// Reads `examples/expected.toml`, whose `[dayNN.<name>]` tables hold the expected
+/// answers for `examples/<name>.txt`, and writes one test per example and part to
+/// `$OUT_DIR/example_tests.rs`. The day includes it with
+/// `include!(concat!(env!("OUT_DIR"), "/example_tests.rs"))`.
+///
+/// Likewise writes `$OUT_DIR/fuzz_tests.rs`, replaying each crashing input that `aoc fuzz`
+/// saved under `fuzz/`, and `$OUT_DIR/answer_tests.rs`, checking each profile's answers from
+/// the nearest `answers.toml`, with its slow parts and those without a readable input ignored.
+pub fn generate_tests() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set"));
+    let package = env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME is not set");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+
+    let examples_dir = manifest_dir.join(EXAMPLES_DIRECTORY);
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let code = get_example_tests(&examples_dir, &package).unwrap_or_else(|error| panic!("{error}"));
+
+    fs::write(out_dir.join(EXAMPLE_TESTS_FILE), code).expect("could not write the example tests");
+
+    let fuzz_dir = manifest_dir.join(FUZZ_DIRECTORY);
+    println!("cargo:rerun-if-changed={}", fuzz_dir.display());
+
+    let code = get_fuzz_tests(&fuzz_dir, &package).unwrap_or_else(|error| panic!("{error}"));
+
+    fs::write(out_dir.join(FUZZ_TESTS_FILE), code).expect("could not write the fuzz tests");
+
+    let answers = match manifest_dir.ancestors().map(|dir| dir.join(ANSWERS_FILE)).find(|path| path.is_file()) {
+        Some(path) => {
+            println!("cargo:rerun-if-changed={}", path.display());
+            Answers::load(&path).unwrap_or_else(|error| panic!("{error}"))
+        }
+        None => Answers::default(),
+    };
+
+    // Build scripts run in the crate's directory, where the tests look for inputs too. The
+    // answer tests are ignored when an input or its key is missing, so look again once either
+    // shows up.
+    let resolver = InputResolver::from_env();
+    let day = package.strip_prefix("day").and_then(|day| day.parse::<u8>().ok()).unwrap_or_default();
+
+    for variable in [INPUT_DIR_VARIABLE, KEY_VARIABLE, KEY_FILE_VARIABLE] {
+        println!("cargo:rerun-if-env-changed={variable}");
+    }
+    for profile in get_answer_profiles(&answers, day) {
+        let profile_dir = resolver.clone().with_profile(profile).get_profile_dir();
+        if profile_dir.is_dir() {
+            println!("cargo:rerun-if-changed={}", profile_dir.display());
+        }
+    }
+    // A missing file would rerun the script on every build, so watch its directory until it exists.
+    if let Some(key_file) = get_key_file() {
+        if key_file.is_file() {
+            println!("cargo:rerun-if-changed={}", key_file.display());
+        } else if let Some(key_dir) = key_file.parent().filter(|key_dir| key_dir.is_dir()) {
+            println!("cargo:rerun-if-changed={}", key_dir.display());
+        }
+    }
+
+    let code = get_answer_tests(&answers, &package, &resolver).unwrap_or_else(|error| panic!("{error}"));
+
+    fs::write(out_dir.join(ANSWER_TESTS_FILE), code).expect("could not write the answer tests");
+}
+
+/// Generates the test functions for a day's examples; `package` is the crate name, such as `day05`.
+pub fn get_example_tests(examples_dir: &Path, package: &str) -> Result<String, String> {
+    let day = package
+        .strip_prefix("day")
+        .and_then(|day| day.parse::<u8>().ok())
+        .ok_or_else(|| format!("{package} is not named dayNN"))?;
+    let solution = format!("crate::Day{day:02}");
+
+    let expected_path = examples_dir.join(EXAMPLES_FILE);
+    let answers = if expected_path.is_file() {
+        Answers::load(&expected_path).map_err(|error| error.to_string())?
+    } else {
+        Answers::default()
+    };
+
+    let mut code = String::new();
+
+    for (expected_day, name, expectation) in answers.iter() {
+        if expected_day != day {
+            return Err(format!("{}: [day{expected_day:02}.{name}] belongs to another day", expected_path.display()));
+        }
+
+        let example_path = examples_dir.join(format!("{name}.txt"));
+        if !example_path.is_file() {
+            return Err(format!("{}: no example file for [day{day:02}.{name}]", example_path.display()));
+        }
+
+        for (part, answer) in &expectation.answers {
+            let ignore = if expectation.slow.contains(part) {
+                "#[ignore = \"slow example, listed under slow in expected.toml\"]\n"
+            } else {
+                ""
+            };
+
+            let _ = write!(
+                code,
+                "#[test]\n{ignore}fn {}_{}() {{\n    aoc_common::assert_example::<{solution}>(include_str!({:?}), aoc_common::Part::{part:?}, {answer:?});\n}}\n\n",
+                name.replace('-', "_"),
+                get_part_key(*part),
+                example_path.to_string_lossy(),
+            );
+        }
+    }
+
+    Ok(code)
+}
+
+/// Finds `dayNN/examples` in `start` or any of its ancestors, where a day binary run from
+/// the workspace, or from within the day's crate, keeps its examples.
+pub fn find_examples_dir(start: &Path, day: u8) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|directory| directory.join(format!("day{day:02}")).join(EXAMPLES_DIRECTORY))
+        .find(|directory| directory.is_dir())
+}
+
+/// Checks one part of a day against an example, panicking on any difference.
+pub fn assert_example<S: Solution>(text: &str, part: Part, expected: &str) {
+    let parsed = S::parse(text).unwrap_or_else(|error| panic!("{}", error.locate(text)));
+    let actual = S::solve(&parsed, part).to_string();
+
+    assert_eq!(actual, expected, "day {} part {part} does not match the example", S::DAY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_examples_dir(name: &str, expected: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-common-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("example_0.txt"), "1\n2\n").unwrap();
+        fs::write(dir.join(EXAMPLES_FILE), expected).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn generated_tests() {
+        let dir = create_examples_dir("examples-generated", "[day07.example_0]\npart_one = 3\npart_two = 6\nslow = [\"part_two\"]\n");
+
+        let code = get_example_tests(&dir, "day07").unwrap();
+
+        assert!(code.contains("fn example_0_part_one()"));
+        assert!(code.contains("assert_example::<crate::Day07>"));
+        assert!(code.contains("aoc_common::Part::One, \"3\""));
+        assert!(code.contains("#[ignore = \"slow example, listed under slow in expected.toml\"]\nfn example_0_part_two()"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn mismatched_examples() {
+        let dir = create_examples_dir("examples-mismatched", "[day07.example_1]\npart_one = 3\n");
+
+        assert!(get_example_tests(&dir, "day07").unwrap_err().contains("no example file"));
+        assert!(get_example_tests(&dir, "day08").unwrap_err().contains("another day"));
+        assert!(get_example_tests(&dir, "aoc").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn examples_dir_of_a_day() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+        assert_eq!(find_examples_dir(&workspace.join("day03").join("src"), 5), Some(workspace.join("day05/examples")));
+        assert_eq!(find_examples_dir(workspace, 99), None);
+    }
+}
@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+use crate::Solution;
+
+/// What `aoc repl` can tell about a day's parsed input beyond its answers, returned by
+/// [`Solution::get_explorer`].
+pub struct Explorer<S: Solution> {
+    /// What `query` takes and shows, for `help`, such as `query <line>  the values of one line`.
+    pub usage: &'static str,
+    /// A few lines summarising the whole input.
+    pub inspect: fn(&S::Input) -> String,
+    /// Answers `query` with the given arguments, or says what it expected instead.
+    pub query: fn(&S::Input, &[&str]) -> Result<String, String>,
+}
+
+/// The query argument at `index`, parsed, or why it could not be.
+pub fn get_argument<T: FromStr>(arguments: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let argument = arguments.get(index).ok_or_else(|| format!("missing {name}"))?;
+
+    argument.parse().map_err(|_| format!("invalid {name}: {argument}"))
+}
+
+/// Looks up the 1-based `name` given as the first query argument.
+pub fn get_item<'a, T>(items: &'a [T], arguments: &[&str], name: &str) -> Result<&'a T, String> {
+    let number: usize = get_argument(arguments, 0, name)?;
+
+    number
+        .checked_sub(1)
+        .and_then(|index| items.get(index))
+        .ok_or_else(|| format!("no {name} {number}, there are {}", items.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments() {
+        assert_eq!(get_argument::<u8>(&["3", "x"], 0, "card"), Ok(3));
+        assert_eq!(get_argument::<u8>(&["3", "x"], 1, "y"), Err("invalid y: x".to_string()));
+        assert_eq!(get_argument::<u8>(&[], 0, "seed"), Err("missing seed".to_string()));
+
+        assert_eq!(get_item(&["a", "b"], &["2"], "line"), Ok(&"b"));
+        assert_eq!(get_item(&["a", "b"], &["0"], "line"), Err("no line 0, there are 2".to_string()));
+    }
+}
@@ -0,0 +1,415 @@
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Write;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Once;
+
+use crate::crypto::{sha256, to_hex};
+use crate::{get_panic_message, Part, Puzzle};
+
+pub const FUZZ_DIRECTORY: &str = "fuzz";
+pub const FUZZ_TESTS_FILE: &str = "fuzz_tests.rs";
+
+/// What a fuzzing run exercises: a day's parser, or the parser followed by one part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FuzzTarget {
+    Parse,
+    Part(Part),
+}
+
+impl FuzzTarget {
+    pub const ALL: [FuzzTarget; 3] = [FuzzTarget::Parse, FuzzTarget::Part(Part::One), FuzzTarget::Part(Part::Two)];
+
+    /// Runs the target on `input`; errors are expected, only a panic is a finding.
+    pub fn run(&self, puzzle: &dyn Puzzle, input: &str) {
+        let parsed = puzzle.parse(input);
+
+        if let (FuzzTarget::Part(part), Ok(parsed)) = (self, parsed) {
+            parsed.solve(*part);
+        }
+    }
+}
+
+impl FromStr for FuzzTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(FuzzTarget::Parse),
+            "part1" => Ok(FuzzTarget::Part(Part::One)),
+            "part2" => Ok(FuzzTarget::Part(Part::Two)),
+            _ => Err(format!("unknown fuzz target: {s}, expected parse, part1 or part2")),
+        }
+    }
+}
+
+impl fmt::Display for FuzzTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuzzTarget::Parse => write!(f, "parse"),
+            FuzzTarget::Part(part) => write!(f, "part{part}"),
+        }
+    }
+}
+
+/// SplitMix64, enough to make fuzzing runs reproducible from a seed.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut value = self.0;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        value ^ (value >> 31)
+    }
+
+    /// Uniform enough in `0..bound`, which must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+const CHARACTERS: &[char] = &[
+    '0', '1', '2', '5', '9', 'a', 'e', 'o', 'z', 'G', 'r', 'b', ' ', ' ', '\n', '\n', ':', ',', ';', '|', '.', '#', '*',
+    '-', '+', '\t', 'é',
+];
+
+const NUMBERS: &[&str] = &[
+    "0", "1", "2", "9", "10", "99", "255", "256", "4294967295", "4294967296", "18446744073709551615",
+    "18446744073709551616", "-1", "",
+];
+
+fn get_number_spans(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = None;
+
+    for (index, char) in chars.iter().chain(std::iter::once(&' ')).enumerate() {
+        match (char.is_ascii_digit(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                spans.push((begin, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+/// Applies one random edit: characters, numbers and whole lines are inserted, removed,
+/// replaced or repeated, or the input is spliced with another one from the corpus.
+pub fn mutate(rng: &mut Rng, input: &str, corpus: &[String]) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let position = |rng: &mut Rng, chars: &Vec<char>| rng.below(chars.len() + 1);
+
+    match rng.below(9) {
+        0 if !chars.is_empty() => {
+            let start = rng.below(chars.len());
+            let end = (start + 1 + rng.below(8)).min(chars.len());
+            chars.drain(start..end);
+        }
+        1 => {
+            let index = position(rng, &chars);
+            chars.insert(index, CHARACTERS[rng.below(CHARACTERS.len())]);
+        }
+        2 if !chars.is_empty() => {
+            let index = rng.below(chars.len());
+            chars[index] = CHARACTERS[rng.below(CHARACTERS.len())];
+        }
+        3 => {
+            let spans = get_number_spans(&chars);
+            let number: Vec<char> = NUMBERS[rng.below(NUMBERS.len())].chars().collect();
+
+            match spans.get(rng.below(spans.len().max(1))) {
+                Some(&(start, end)) => {
+                    chars.splice(start..end, number);
+                }
+                None => {
+                    let index = position(rng, &chars);
+                    chars.splice(index..index, number);
+                }
+            }
+        }
+        4 | 5 => {
+            let mut lines: Vec<String> = input.split('\n').map(str::to_string).collect();
+            let index = rng.below(lines.len());
+
+            match rng.below(3) {
+                0 => {
+                    let line = lines[index].clone();
+                    lines.insert(index, line);
+                }
+                1 if lines.len() > 1 => {
+                    lines.remove(index);
+                }
+                _ => {
+                    let other = rng.below(lines.len());
+                    lines.swap(index, other);
+                }
+            }
+
+            return lines.join("\n");
+        }
+        6 if !corpus.is_empty() => {
+            let other: Vec<char> = corpus[rng.below(corpus.len())].chars().collect();
+            let split = position(rng, &chars);
+            let other_split = rng.below(other.len() + 1);
+
+            chars.truncate(split);
+            chars.extend_from_slice(&other[other_split..]);
+        }
+        7 if !chars.is_empty() => {
+            let start = rng.below(chars.len());
+            let end = (start + 1 + rng.below(16)).min(chars.len());
+            let repeated: Vec<char> = chars[start..end].to_vec();
+            let times = 1 + rng.below(4);
+
+            for _ in 0..times {
+                chars.splice(end..end, repeated.iter().copied());
+            }
+        }
+        _ => {
+            let length = rng.below(chars.len() + 1);
+            chars.truncate(length);
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// An input that made a target panic, with where and why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub target: FuzzTarget,
+    pub input: String,
+    pub message: String,
+}
+
+impl Finding {
+    /// `<target>-<hash>.txt`, stable for the same input, so a crash found twice is saved once.
+    pub fn get_file_name(&self) -> String {
+        format!("{}-{}.txt", self.target, &to_hex(&sha256(&[self.input.as_bytes()]))[..12])
+    }
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, returning the panic message and location if it panicked. Expected panics would
+/// flood stderr, so on this thread the hook records only where each one happened; panics
+/// elsewhere still reach the previous hook.
+pub(crate) fn catch_quietly<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if QUIET.with(Cell::get) {
+                let location = info.location().map(|location| format!("{}:{}", location.file(), location.line()));
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                hook(info);
+            }
+        }));
+    });
+
+    let was_quiet = QUIET.with(|quiet| quiet.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(was_quiet));
+
+    result.map_err(|payload| match PANIC_LOCATION.with(|cell| cell.borrow_mut().take()) {
+        Some(location) => format!("{} at {location}", get_panic_message(&*payload)),
+        None => get_panic_message(&*payload),
+    })
+}
+
+/// Mutates inputs from `corpus` `runs` times and feeds them to `target`, returning one finding
+/// per distinct panic. Inputs that parse are kept as further seeds, as they reach deeper.
+pub fn fuzz(
+    puzzle: &dyn Puzzle,
+    target: FuzzTarget,
+    corpus: &[String],
+    runs: usize,
+    seed: u64,
+    max_len: usize,
+) -> Vec<Finding> {
+    let mut rng = Rng::new(seed);
+    let mut corpus: Vec<String> = corpus.to_vec();
+    if corpus.is_empty() {
+        corpus.push(String::new());
+    }
+    let seeds = corpus.len();
+
+    let mut messages = BTreeSet::new();
+    let mut findings = vec![];
+
+    for _ in 0..runs {
+        let mut input = corpus[rng.below(corpus.len())].clone();
+        for _ in 0..1 + rng.below(4) {
+            input = mutate(&mut rng, &input, &corpus);
+        }
+
+        if input.len() > max_len {
+            let mut end = max_len;
+            while !input.is_char_boundary(end) {
+                end -= 1;
+            }
+            input.truncate(end);
+        }
+
+        match catch_quietly(|| target.run(puzzle, &input)) {
+            Err(message) if messages.insert(message.clone()) => findings.push(Finding {
+                target,
+                input,
+                message,
+            }),
+            Ok(()) if corpus.len() < seeds + 256 && puzzle.parse(&input).is_ok() => corpus.push(input),
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+/// Generates one test per crashing input saved in `fuzz_dir` as `<target>-<hash>.txt`, each
+/// replaying it against `crate::DayNN`; `package` is the crate name, such as `day05`.
+pub fn get_fuzz_tests(fuzz_dir: &Path, package: &str) -> Result<String, String> {
+    let day = package
+        .strip_prefix("day")
+        .and_then(|day| day.parse::<u8>().ok())
+        .ok_or_else(|| format!("{package} is not named dayNN"))?;
+
+    let mut paths: Vec<_> = fs::read_dir(fuzz_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    paths.sort();
+
+    let mut code = String::new();
+
+    for path in paths {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let (target, hash) = name
+            .split_once('-')
+            .ok_or_else(|| format!("{}: expected a <target>-<hash>.txt name", path.display()))?;
+        target.parse::<FuzzTarget>().map_err(|error| format!("{}: {error}", path.display()))?;
+
+        let _ = write!(
+            code,
+            "#[test]\nfn {target}_{}() {{\n    aoc_common::replay_fuzz_input(&crate::Day{day:02}, {target:?}, include_str!({:?}));\n}}\n\n",
+            hash.replace(|char: char| !char.is_ascii_alphanumeric(), "_"),
+            path.to_string_lossy(),
+        );
+    }
+
+    Ok(code)
+}
+
+/// Runs a saved fuzzing input again, panicking as it did until the day is fixed.
+pub fn replay_fuzz_input(puzzle: &dyn Puzzle, target: &str, input: &str) {
+    let target: FuzzTarget = target.parse().unwrap_or_else(|error| panic!("{error}"));
+
+    target.run(puzzle, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_number, Answer, ParseError, Solution};
+    use std::env;
+
+    struct Fragile;
+
+    impl Solution for Fragile {
+        const DAY: u8 = 7;
+        const TITLE: &'static str = "Fragile";
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split_whitespace().map(parse_number).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.iter().map(|&number| u64::from(number)).sum::<u64>().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            (input[0] / input[input.len() - 1]).into()
+        }
+    }
+
+    #[test]
+    fn targets() {
+        assert_eq!("part2".parse(), Ok(FuzzTarget::Part(Part::Two)));
+        assert_eq!(FuzzTarget::Parse.to_string(), "parse");
+        assert!("part3".parse::<FuzzTarget>().is_err());
+    }
+
+    #[test]
+    fn mutations_are_reproducible() {
+        let corpus = vec!["Time: 7 15\nDistance: 9 40".to_string()];
+        let mutations = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..50).map(|_| mutate(&mut rng, &corpus[0], &corpus)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(mutations(1), mutations(1));
+        assert_ne!(mutations(1), mutations(2));
+        assert!(mutations(1).iter().any(|input| input != &corpus[0]));
+    }
+
+    #[test]
+    fn finds_panics_once_each() {
+        let findings = fuzz(&Fragile, FuzzTarget::Part(Part::Two), &["4 2".to_string()], 2000, 7, 64);
+
+        assert!(!findings.is_empty());
+        assert!(findings.iter().all(|finding| finding.target == FuzzTarget::Part(Part::Two)));
+        assert!(findings
+            .iter()
+            .any(|finding| finding.message.starts_with("attempt to divide by zero at ") || finding.message.contains("index out of bounds")));
+
+        let messages: BTreeSet<&str> = findings.iter().map(|finding| finding.message.as_str()).collect();
+        assert_eq!(messages.len(), findings.len());
+
+        assert!(fuzz(&Fragile, FuzzTarget::Part(Part::One), &["4 2".to_string()], 500, 7, 64).is_empty());
+    }
+
+    #[test]
+    fn generated_tests() {
+        let dir = env::temp_dir().join(format!("aoc-common-fuzz-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let finding = Finding {
+            target: FuzzTarget::Part(Part::Two),
+            input: "0".to_string(),
+            message: String::new(),
+        };
+        fs::write(dir.join(finding.get_file_name()), &finding.input).unwrap();
+        fs::write(dir.join("notes.md"), "").unwrap();
+
+        let code = get_fuzz_tests(&dir, "day07").unwrap();
+        assert!(code.starts_with(&format!("#[test]\nfn part2_{}()", &finding.get_file_name()[6..18])));
+        assert!(code.contains("replay_fuzz_input(&crate::Day07, \"part2\", include_str!("));
+
+        fs::write(dir.join("part9-abc.txt"), "").unwrap();
+        assert!(get_fuzz_tests(&dir, "day07").unwrap_err().contains("unknown fuzz target"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Args, Part, Rng};
+
+/// A synthetic input, and the answers worked out while building it rather than by the solver.
+/// A part is missing when its answer does not fit in 64 bits.
+pub struct Generated {
+    pub input: String,
+    pub answers: BTreeMap<Part, String>,
+}
+
+/// How `aoc generate` builds inputs for a day, returned by [`Solution::get_generator`](crate::Solution::get_generator).
+///
+/// A generator works out the answers while it builds the input, in its own way rather than
+/// the solver's, so that comparing the two can catch a mistake in either.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    /// The size options `generate` reads, for the usage, such as `--lines <n> --tokens <n>`.
+    pub options: &'static str,
+    /// Builds an input, taking its size options from the arguments.
+    pub generate: fn(&mut Rng, &mut Args) -> Result<Generated, String>,
+}
+
+/// A size option, at least `min`, `default` when not given.
+pub fn get_size<T: FromStr + PartialOrd + fmt::Display>(args: &mut Args, name: &str, default: T, min: T) -> Result<T, String> {
+    let value = args.parsed_option::<T>(name)?.unwrap_or(default);
+
+    if value < min {
+        return Err(format!("{name} must be at least {min}"));
+    }
+
+    Ok(value)
+}
+
+/// Uniform enough in `low..=high`.
+pub fn get_between(rng: &mut Rng, low: u64, high: u64) -> u64 {
+    match (high - low).checked_add(1) {
+        Some(bound) => low + rng.next_u64() % bound,
+        None => rng.next_u64(),
+    }
+}
+
+/// The answers of both parts, leaving out those that did not fit.
+pub fn get_answers(part_one: Option<u64>, part_two: Option<u64>) -> BTreeMap<Part, String> {
+    [(Part::One, part_one), (Part::Two, part_two)]
+        .into_iter()
+        .filter_map(|(part, answer)| Some((part, answer?.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        let mut args = Args::new(["--lines".to_string(), "0".to_string()]);
+
+        assert_eq!(get_size(&mut args, "--lines", 10, 1), Err("--lines must be at least 1".to_string()));
+        assert_eq!(get_size(&mut args, "--tokens", 12, 1), Ok(12));
+    }
+
+    #[test]
+    fn bounds() {
+        let mut rng = Rng::new(1);
+
+        assert!((0..100).all(|_| (5..=7).contains(&get_between(&mut rng, 5, 7))));
+        assert_eq!(get_between(&mut rng, 3, 3), 3);
+        get_between(&mut rng, 0, u64::MAX);
+
+        assert_eq!(get_answers(Some(1), None).len(), 1);
+    }
+}
@@ -0,0 +1,373 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::encryption::{get_encrypted_path, is_encrypted_path, EncryptionError, Key};
+use crate::{is_profile_name, PUZZLE_INPUT};
+
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+pub const INPUTS_DIRECTORY: &str = "inputs";
+pub const PROFILE_VARIABLE: &str = "AOC_PROFILE";
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { day: u8, searched: Vec<PathBuf> },
+    Io(PathBuf, io::Error),
+    Stdin(io::Error),
+    Encryption(PathBuf, EncryptionError),
+    InvalidProfile(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, searched } => {
+                write!(f, "no input found for day {day}, looked in:")?;
+                for path in searched {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                write!(f, "\npass a path, `-` for stdin, or set {INPUT_DIR_VARIABLE}")
+            }
+            InputError::Io(path, error) => write!(f, "{}: {error}", path.display()),
+            InputError::Stdin(error) => write!(f, "stdin: {error}"),
+            InputError::Encryption(path, error) => write!(f, "{}: {error}", path.display()),
+            InputError::InvalidProfile(profile) => {
+                write!(f, "invalid profile {profile:?}, use letters, digits, `-` and `_`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl InputError {
+    /// The input exists, but only encrypted, and there is no key to read it with.
+    pub fn is_missing_key(&self) -> bool {
+        matches!(self, InputError::Encryption(_, EncryptionError::MissingKey(_)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Finds a day's puzzle input at runtime.
+///
+/// In order: an explicit path (`-` meaning stdin), `$AOC_INPUT_DIR/dayNN.txt`,
+/// then `inputs/dayNN.txt` in the search root or any of its ancestors. In each
+/// directory an encrypted `dayNN.txt.enc` is used when there is no plain copy.
+///
+/// With a profile other than the default `puzzle` one, every directory is replaced
+/// by its `<profile>` subdirectory, so each account keeps its own inputs.
+#[derive(Clone)]
+pub struct InputResolver {
+    input_dir: Option<PathBuf>,
+    search_root: PathBuf,
+    profile: Option<String>,
+}
+
+impl InputResolver {
+    pub fn new(input_dir: Option<PathBuf>, search_root: PathBuf) -> Self {
+        InputResolver {
+            input_dir,
+            search_root,
+            profile: None,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let input_dir = env::var_os(INPUT_DIR_VARIABLE)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+        let search_root = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+        let resolver = InputResolver::new(input_dir, search_root);
+
+        match env::var(PROFILE_VARIABLE) {
+            Ok(profile) if !profile.is_empty() => resolver.with_profile(&profile),
+            _ => resolver,
+        }
+    }
+
+    pub fn with_profile(mut self, profile: &str) -> Self {
+        self.profile = Some(profile.to_string()).filter(|profile| profile != PUZZLE_INPUT);
+        self
+    }
+
+    pub fn get_profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or(PUZZLE_INPUT)
+    }
+
+    /// Directory the current profile's inputs are stored in.
+    pub fn get_profile_dir(&self) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.get_input_dir().join(profile),
+            None => self.get_input_dir(),
+        }
+    }
+
+    /// Every profile with at least one input in the input directory, the default one included.
+    pub fn get_profiles(&self) -> Vec<String> {
+        let input_dir = self.get_input_dir();
+        let has_inputs = |directory: &Path| {
+            (1..=25).any(|day| {
+                let path = directory.join(get_file_name(day));
+                path.is_file() || get_encrypted_path(&path).is_file()
+            })
+        };
+
+        let mut profiles = vec![];
+
+        if has_inputs(&input_dir) {
+            profiles.push(PUZZLE_INPUT.to_string());
+        }
+
+        if let Ok(entries) = fs::read_dir(&input_dir) {
+            let mut named: Vec<String> = entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir() && has_inputs(&entry.path()))
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| is_profile_name(name) && name != PUZZLE_INPUT)
+                .collect();
+
+            named.sort();
+            profiles.extend(named);
+        }
+
+        profiles
+    }
+
+    /// Directory new inputs are stored in: `$AOC_INPUT_DIR`, else the nearest existing
+    /// `inputs` directory, else `inputs` in the search root.
+    pub fn get_input_dir(&self) -> PathBuf {
+        if let Some(input_dir) = &self.input_dir {
+            return input_dir.clone();
+        }
+
+        self.search_root
+            .ancestors()
+            .map(|directory| directory.join(INPUTS_DIRECTORY))
+            .find(|directory| directory.is_dir())
+            .unwrap_or_else(|| self.search_root.join(INPUTS_DIRECTORY))
+    }
+
+    pub fn get_candidates(&self, day: u8) -> Vec<PathBuf> {
+        let file_name = get_file_name(day);
+        let mut candidates = vec![];
+
+        let directories = self
+            .input_dir
+            .iter()
+            .cloned()
+            .chain(self.search_root.ancestors().map(|directory| directory.join(INPUTS_DIRECTORY)));
+
+        for directory in directories {
+            let directory = match &self.profile {
+                Some(profile) => directory.join(profile),
+                None => directory,
+            };
+            let path = directory.join(&file_name);
+            let encrypted = get_encrypted_path(&path);
+
+            candidates.extend([path, encrypted]);
+        }
+
+        candidates
+    }
+
+    pub fn resolve(&self, day: u8, explicit: Option<&str>) -> Result<InputSource, InputError> {
+        match explicit {
+            Some("-") => return Ok(InputSource::Stdin),
+            Some(path) => return Ok(InputSource::Path(PathBuf::from(path))),
+            None => {}
+        }
+
+        if let Some(profile) = self.profile.as_ref().filter(|profile| !is_profile_name(profile)) {
+            return Err(InputError::InvalidProfile(profile.clone()));
+        }
+
+        let candidates = self.get_candidates(day);
+
+        match candidates.iter().find(|candidate| candidate.is_file()) {
+            Some(path) => Ok(InputSource::Path(path.clone())),
+            None => Err(InputError::NotFound {
+                day,
+                searched: candidates,
+            }),
+        }
+    }
+
+    pub fn read(&self, day: u8, explicit: Option<&str>) -> Result<String, InputError> {
+        read_source(&self.resolve(day, explicit)?)
+    }
+
+    /// Why the day's input cannot be read, without reading it: there is none, or it is only
+    /// stored encrypted and there is no key. `None` when reading is worth trying.
+    pub fn get_unavailable_reason(&self, day: u8) -> Option<String> {
+        match self.resolve(day, None) {
+            Ok(InputSource::Path(path)) if is_encrypted_path(&path) => match Key::from_env() {
+                Err(error @ EncryptionError::MissingKey(_)) => Some(format!("{} is encrypted: {error}", path.display())),
+                _ => None,
+            },
+            Err(error @ InputError::NotFound { .. }) => error.to_string().lines().next().map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_file_name(day: u8) -> String {
+    format!("day{day:02}.txt")
+}
+
+pub fn read_source(source: &InputSource) -> Result<String, InputError> {
+    match source {
+        InputSource::Path(path) => read_path(path),
+        InputSource::Stdin => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(InputError::Stdin)?;
+
+            Ok(text)
+        }
+    }
+}
+
+fn read_path(path: &Path) -> Result<String, InputError> {
+    if !is_encrypted_path(path) {
+        return fs::read_to_string(path).map_err(|error| InputError::Io(path.to_path_buf(), error));
+    }
+
+    let data = fs::read(path).map_err(|error| InputError::Io(path.to_path_buf(), error))?;
+    let encryption_error = |error| InputError::Encryption(path.to_path_buf(), error);
+
+    let text = Key::from_env().and_then(|key| key.decrypt(&data)).map_err(encryption_error)?;
+
+    String::from_utf8(text).map_err(|error| {
+        InputError::Io(path.to_path_buf(), io::Error::new(io::ErrorKind::InvalidData, error))
+    })
+}
+
+/// Reads a day's input through the default resolver.
+pub fn read_input(day: u8) -> Result<String, InputError> {
+    InputResolver::from_env().read(day, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn explicit_path_and_stdin() {
+        let resolver = InputResolver::new(None, PathBuf::from("/nonexistent"));
+
+        assert_eq!(resolver.resolve(1, Some("-")).unwrap(), InputSource::Stdin);
+        assert_eq!(
+            resolver.resolve(1, Some("mine.txt")).unwrap(),
+            InputSource::Path(PathBuf::from("mine.txt"))
+        );
+    }
+
+    #[test]
+    fn input_dir_before_conventional_location() {
+        let root = create_temp_dir("order");
+        let custom = root.join("custom");
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&custom).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(root.join(INPUTS_DIRECTORY)).unwrap();
+        fs::write(root.join(INPUTS_DIRECTORY).join("day05.txt"), "conventional").unwrap();
+        fs::write(custom.join("day05.txt"), "custom").unwrap();
+
+        let resolver = InputResolver::new(None, nested.clone());
+        assert_eq!(resolver.read(5, None).unwrap(), "conventional");
+
+        assert_eq!(resolver.get_input_dir(), root.join(INPUTS_DIRECTORY));
+
+        let resolver = InputResolver::new(Some(custom.clone()), nested);
+        assert_eq!(resolver.read(5, None).unwrap(), "custom");
+        assert_eq!(resolver.get_input_dir(), custom);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn not_found_lists_searched_locations() {
+        let root = create_temp_dir("missing");
+
+        let resolver = InputResolver::new(Some(root.join("custom")), root.clone());
+        let error = resolver.read(7, None).unwrap_err();
+        let message = error.to_string();
+
+        assert!(message.contains("no input found for day 7"));
+        assert!(message.contains(&root.join("custom").join("day07.txt").display().to_string()));
+        assert!(message.contains(&root.join("inputs").join("day07.txt").display().to_string()));
+        assert_eq!(resolver.get_unavailable_reason(7).as_deref(), Some("no input found for day 7, looked in:"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn encrypted_input_when_no_plain_copy() {
+        let root = create_temp_dir("encrypted");
+        let inputs = root.join(INPUTS_DIRECTORY);
+        fs::create_dir_all(&inputs).unwrap();
+        fs::write(inputs.join("day04.txt.enc"), "encrypted").unwrap();
+
+        let resolver = InputResolver::new(None, root.clone());
+        assert_eq!(resolver.resolve(4, None).unwrap(), InputSource::Path(inputs.join("day04.txt.enc")));
+
+        fs::write(inputs.join("day04.txt"), "plain").unwrap();
+        assert_eq!(resolver.resolve(4, None).unwrap(), InputSource::Path(inputs.join("day04.txt")));
+        assert_eq!(resolver.get_unavailable_reason(4), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn profiles() {
+        let root = create_temp_dir("profiles");
+        let inputs = root.join(INPUTS_DIRECTORY);
+        fs::create_dir_all(inputs.join("alice")).unwrap();
+        fs::create_dir_all(inputs.join("bob")).unwrap();
+        fs::create_dir_all(inputs.join("empty")).unwrap();
+        fs::write(inputs.join("day03.txt"), "default").unwrap();
+        fs::write(inputs.join("alice").join("day03.txt"), "alice").unwrap();
+        fs::write(inputs.join("bob").join("day03.txt.enc"), "bob").unwrap();
+
+        let resolver = InputResolver::new(None, root.clone());
+        assert_eq!(resolver.get_profiles(), vec!["puzzle", "alice", "bob"]);
+        assert_eq!(resolver.read(3, None).unwrap(), "default");
+        assert_eq!(resolver.clone().with_profile("puzzle").read(3, None).unwrap(), "default");
+
+        let alice = resolver.clone().with_profile("alice");
+        assert_eq!(alice.read(3, None).unwrap(), "alice");
+        assert_eq!(alice.get_profile_dir(), inputs.join("alice"));
+        assert!(matches!(alice.read(4, None), Err(InputError::NotFound { .. })));
+
+        let invalid = resolver.with_profile("../alice");
+        assert!(matches!(invalid.read(3, None), Err(InputError::InvalidProfile(_))));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
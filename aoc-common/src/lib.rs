@@ -0,0 +1,56 @@
+mod allocations;
+mod answer;
+mod answers;
+mod args;
+mod binary;
+mod crypto;
+mod encryption;
+mod examples;
+mod explore;
+mod fuzz;
+mod generate;
+mod input;
+mod json;
+mod parse_error;
+mod property;
+mod puzzle;
+mod record;
+mod runner;
+mod solution;
+mod trace;
+mod watch;
+
+pub use allocations::{
+    count_allocations, enable_allocation_counting, format_bytes, is_counting_allocations, Allocations, CountingAllocator,
+};
+pub use answer::{Answer, OVERFLOW_REASON};
+pub use answers::{
+    assert_answer, get_answer_profiles, get_answer_tests, get_part_key, is_profile_name, Answers, AnswersError, Expectation,
+    ANSWERS_FILE, ANSWER_TESTS_FILE, PUZZLE_INPUT,
+};
+pub use args::Args;
+pub use binary::day_main;
+pub use encryption::{
+    get_encrypted_path, get_key_file, is_encrypted_path, EncryptionError, Key, ENCRYPTED_EXTENSION, KEY_FILE_VARIABLE,
+    KEY_VARIABLE,
+};
+pub use examples::{
+    assert_example, find_examples_dir, generate_tests, get_example_tests, EXAMPLES_DIRECTORY, EXAMPLES_FILE, EXAMPLE_TESTS_FILE,
+};
+pub use explore::{get_argument, get_item, Explorer};
+pub use fuzz::{
+    fuzz, get_fuzz_tests, mutate, replay_fuzz_input, Finding, FuzzTarget, Rng, FUZZ_DIRECTORY, FUZZ_TESTS_FILE,
+};
+pub use generate::{get_answers, get_between, get_size, Generated, Generator};
+pub use input::{
+    get_file_name, read_input, read_source, InputError, InputResolver, InputSource, INPUT_DIR_VARIABLE, PROFILE_VARIABLE,
+};
+pub use json::Json;
+pub use parse_error::{parse_number, parse_or_panic, Location, ParseError};
+pub use property::{check, check_with, Config, Source, CASES_VARIABLE, SEED_VARIABLE};
+pub use puzzle::{generate_registry, get_day_dependencies, get_registry_code, Parsed, Puzzle, Registry, REGISTRY_FILE};
+pub use record::{format_records, get_panic_message, get_records, Format, Record, CSV_HEADER};
+pub use runner::{format_duration, run, PartReport, Report};
+pub use solution::{Part, Solution};
+pub use trace::{init_tracing, is_enabled, set_level, write_event, Level, Span, LOG_VARIABLE};
+pub use watch::{watch, DEFAULT_WATCH_INTERVAL};
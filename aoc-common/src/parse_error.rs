@@ -0,0 +1,201 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Where in the input a [`ParseError`] happened, with the whole offending line for the snippet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+}
+
+/// Malformed puzzle input, pointing at the offending text.
+///
+/// Parsers create it from a slice of the text they were given. Once the error reaches
+/// the code holding the whole input, [`ParseError::locate`] turns that slice into a line
+/// and column, and the error renders as a snippet with the slice underlined.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    message: String,
+    text: String,
+    address: usize,
+    location: Option<Box<Location>>,
+    file: Option<String>,
+    empty_input: bool,
+}
+
+impl ParseError {
+    /// `text` should be a slice of the input being parsed, so that it can be located later.
+    pub fn new(text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            message: message.into(),
+            text: text.to_string(),
+            address: text.as_ptr() as usize,
+            location: None,
+            file: None,
+            empty_input: false,
+        }
+    }
+
+    /// The input had nothing to parse at all, as opposed to text that is malformed.
+    pub fn empty_input(text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            empty_input: true,
+            ..ParseError::new(text, message)
+        }
+    }
+
+    /// Points just past the end of `text`, for something missing after it.
+    pub fn after(text: &str, message: impl Into<String>) -> Self {
+        ParseError::new(&text[text.len()..], message)
+    }
+
+    /// Works out the line and column of the offending text, if it is part of `input`.
+    pub fn locate(mut self, input: &str) -> Self {
+        if self.location.is_some() {
+            return self;
+        }
+
+        let start = input.as_ptr() as usize;
+        let Some(offset) = self.address.checked_sub(start) else {
+            return self;
+        };
+
+        if offset + self.text.len() > input.len() || !input.is_char_boundary(offset) {
+            return self;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |index| offset + index);
+
+        self.location = Some(Box::new(Location {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end].trim_end_matches('\r').to_string(),
+        }));
+
+        self
+    }
+
+    /// Names the file the input came from in the rendered error.
+    pub fn with_file(mut self, file: impl fmt::Display) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn get_location(&self) -> Option<&Location> {
+        self.location.as_deref()
+    }
+
+    pub fn is_empty_input(&self) -> bool {
+        self.empty_input
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+
+        let Some(location) = &self.location else {
+            if self.file.is_some() {
+                write!(f, " ")?;
+            }
+
+            if self.text.is_empty() {
+                return write!(f, "{}", self.message);
+            }
+
+            return write!(f, "{}: {:?}", self.message, self.text);
+        };
+
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let underline = "^".repeat(self.text.lines().next().unwrap_or("").chars().count().max(1));
+
+        writeln!(f, "{}:{}: {}", location.line, location.column, self.message)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", location.source_line)?;
+        write!(f, "{gutter} | {}{underline}", " ".repeat(location.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a whole token as a number, or explains what was wrong with it.
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.parse::<T>().map_err(|_| {
+        if text.is_empty() {
+            ParseError::new(text, "expected a number")
+        } else {
+            ParseError::new(text, "invalid number")
+        }
+    })
+}
+
+/// Parses a model from test text, panicking with the located error if it is malformed.
+pub fn parse_or_panic<T: FromStr<Err = ParseError>>(text: &str) -> T {
+    text.parse().unwrap_or_else(|error: ParseError| panic!("{}", error.locate(text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "seeds: 79 14\n\nseed-to-soil map:\n50 9x8 2\n";
+
+    #[test]
+    fn snippet() {
+        let token = INPUT.split_whitespace().find(|token| *token == "9x8").unwrap();
+
+        let error = parse_number::<u64>(token).unwrap_err().locate(INPUT).with_file("inputs/day05.txt");
+
+        assert_eq!(
+            error.get_location(),
+            Some(&Location {
+                line: 4,
+                column: 4,
+                source_line: "50 9x8 2".to_string(),
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "inputs/day05.txt:4:4: invalid number\n  |\n4 | 50 9x8 2\n  |    ^^^"
+        );
+    }
+
+    #[test]
+    fn missing_text_after() {
+        let line = INPUT.lines().next().unwrap();
+
+        let error = ParseError::after(line, "expected a third seed").locate(INPUT);
+
+        assert_eq!(error.to_string(), "1:13: expected a third seed\n  |\n1 | seeds: 79 14\n  |             ^");
+    }
+
+    #[test]
+    fn outside_of_input() {
+        let owned = "12a".to_string();
+
+        let error = parse_number::<u32>(&owned).unwrap_err().locate(INPUT);
+
+        assert_eq!(error.get_location(), None);
+        assert_eq!(error.to_string(), "invalid number: \"12a\"");
+        assert_eq!(ParseError::new("", "expected a game").with_file("<stdin>").to_string(), "<stdin>: expected a game");
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(ParseError::empty_input("", "expected seeds").is_empty_input());
+        assert!(!parse_number::<u32>("").unwrap_err().is_empty_input());
+    }
+}
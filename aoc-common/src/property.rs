@@ -0,0 +1,426 @@
+use std::cell::Cell;
+use std::env;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::fuzz::catch_quietly;
+use crate::Rng;
+
+pub const CASES_VARIABLE: &str = "AOC_PROPERTY_CASES";
+pub const SEED_VARIABLE: &str = "AOC_PROPERTY_SEED";
+
+const DEFAULT_CASES: usize = 256;
+const DEFAULT_SEED: u64 = 2023;
+
+/// How many candidates shrinking may try before it settles for the smallest failure so far.
+const MAX_SHRINK_ATTEMPTS: usize = 5000;
+
+/// Where generators draw their values from: fresh random choices, or recorded ones replayed.
+///
+/// Every value is built from a sequence of choices, and a generator always turns the same
+/// choices into the same value. Shrinking edits the choices rather than the value, deleting
+/// and lowering them, so any generator shrinks without code of its own. A choice of zero is
+/// the simplest one: the start of a range, `false`, the first item, or a shorter vector.
+pub struct Source {
+    choices: Vec<u64>,
+    position: usize,
+    rng: Option<Rng>,
+}
+
+impl Source {
+    fn random(seed: u64) -> Self {
+        Source {
+            choices: vec![],
+            position: 0,
+            rng: Some(Rng::new(seed)),
+        }
+    }
+
+    fn replay(choices: &[u64]) -> Self {
+        Source {
+            choices: choices.to_vec(),
+            position: 0,
+            rng: None,
+        }
+    }
+
+    /// The choices consumed so far; replayed sources run out into zeros.
+    fn into_choices(mut self) -> Vec<u64> {
+        self.choices.truncate(self.position);
+        self.choices
+    }
+
+    /// A choice in `0..=max`.
+    fn draw(&mut self, max: u64) -> u64 {
+        let value = match (self.choices.get(self.position), &mut self.rng) {
+            (Some(&choice), _) => choice.min(max),
+            (None, Some(rng)) => match max.checked_add(1) {
+                Some(bound) => rng.next_u64() % bound,
+                None => rng.next_u64(),
+            },
+            (None, None) => 0,
+        };
+
+        match self.choices.get_mut(self.position) {
+            Some(choice) => *choice = value,
+            None => self.choices.push(value),
+        }
+        self.position += 1;
+
+        value
+    }
+
+    pub fn u64_in(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+
+        start + self.draw(end - start)
+    }
+
+    pub fn u32_in(&mut self, range: RangeInclusive<u32>) -> u32 {
+        let (start, end) = range.into_inner();
+
+        self.u64_in(u64::from(start)..=u64::from(end)) as u32
+    }
+
+    pub fn usize_in(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+
+        self.u64_in(start as u64..=end as u64) as usize
+    }
+
+    pub fn i32_in(&mut self, range: RangeInclusive<i32>) -> i32 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+
+        (i64::from(start) + self.draw((i64::from(end) - i64::from(start)) as u64) as i64) as i32
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.draw(1) == 1
+    }
+
+    /// One of `items`, which must not be empty; shrinks towards the first.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        assert!(!items.is_empty(), "nothing to choose from");
+
+        &items[self.draw(items.len() as u64 - 1) as usize]
+    }
+
+    /// A vector of `length` elements from `element`. Past the minimum, each further element is
+    /// preceded by a choice to continue, which shrinking can delete along with the element.
+    pub fn vec_of<T>(&mut self, length: RangeInclusive<usize>, mut element: impl FnMut(&mut Source) -> T) -> Vec<T> {
+        let (min, max) = length.into_inner();
+        let mut values = Vec::with_capacity(min);
+
+        while values.len() < min || (values.len() < max && self.draw(7) != 0) {
+            values.push(element(self));
+        }
+
+        values
+    }
+}
+
+/// How many cases a property runs and from which seed, `$AOC_PROPERTY_CASES` and
+/// `$AOC_PROPERTY_SEED` unless set in code.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        fn get<T: std::str::FromStr>(name: &str, default: T) -> T {
+            match env::var(name) {
+                Ok(value) => value.trim().parse().unwrap_or_else(|_| panic!("invalid {name}: {value}")),
+                Err(_) => default,
+            }
+        }
+
+        Config {
+            cases: get(CASES_VARIABLE, DEFAULT_CASES),
+            seed: get(SEED_VARIABLE, DEFAULT_SEED),
+        }
+    }
+}
+
+/// The smallest failing case found, with what it takes to replay it.
+#[derive(Debug)]
+struct Failure {
+    case: usize,
+    seed: u64,
+    value: String,
+    message: String,
+    original_choices: usize,
+    choices: usize,
+    shrinks: usize,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "failed on case {} with seed {}, shrunk from {} to {} choices in {} steps:",
+            self.case + 1,
+            self.seed,
+            self.original_choices,
+            self.choices,
+            self.shrinks
+        )?;
+        writeln!(f, "{}", self.value)?;
+        writeln!(f, "{}", self.message)?;
+        write!(f, "rerun it alone with {SEED_VARIABLE}={} {CASES_VARIABLE}=1", self.seed)
+    }
+}
+
+/// Runs `generate` and then `property` on the choices, returning the panic message of either.
+fn run_case<T>(choices: &mut Source, generate: &impl Fn(&mut Source) -> T, property: &impl Fn(&T)) -> Result<(), String> {
+    catch_quietly(|| {
+        let value = generate(choices);
+        property(&value);
+    })
+}
+
+/// Whether `candidate` is simpler: fewer choices, or as many but smaller ones.
+fn is_simpler(candidate: &[u64], current: &[u64]) -> bool {
+    (candidate.len(), candidate) < (current.len(), current)
+}
+
+fn shrink<T>(
+    mut choices: Vec<u64>,
+    mut message: String,
+    generate: &impl Fn(&mut Source) -> T,
+    property: &impl Fn(&T),
+) -> (Vec<u64>, String, usize) {
+    let attempts = Cell::new(0);
+    let shrinks = Cell::new(0);
+
+    // Replays `candidate`, keeping the choices it consumed when it still fails and is simpler.
+    let attempt = |candidate: Vec<u64>, choices: &mut Vec<u64>, message: &mut String| -> bool {
+        if attempts.get() >= MAX_SHRINK_ATTEMPTS {
+            return false;
+        }
+        attempts.set(attempts.get() + 1);
+
+        let mut source = Source::replay(&candidate);
+        let Err(error) = run_case(&mut source, generate, property) else {
+            return false;
+        };
+
+        let consumed = source.into_choices();
+        if !is_simpler(&consumed, choices) {
+            return false;
+        }
+
+        *choices = consumed;
+        *message = error;
+        shrinks.set(shrinks.get() + 1);
+        true
+    };
+
+    loop {
+        let mut improved = false;
+
+        for size in [8, 4, 2, 1] {
+            let mut start = 0;
+
+            while start + size <= choices.len() {
+                let mut candidate = choices.clone();
+                candidate.drain(start..start + size);
+
+                if attempt(candidate, &mut choices, &mut message) {
+                    improved = true;
+                } else {
+                    start += 1;
+                }
+            }
+        }
+
+        let mut index = 0;
+
+        while index < choices.len() {
+            let length = choices.len();
+            let (mut low, mut high) = (0, choices[index]);
+
+            // Binary search for the lowest value of this choice that still fails.
+            while low < high && choices.len() == length {
+                let middle = low + (high - low) / 2;
+                let mut candidate = choices.clone();
+                candidate[index] = middle;
+
+                if attempt(candidate, &mut choices, &mut message) {
+                    improved = true;
+                    high = choices.get(index).copied().unwrap_or(0);
+                } else {
+                    low = middle + 1;
+                }
+            }
+
+            index += 1;
+        }
+
+        // Moving a value onto a later choice lets, say, three numbers over a sum become two.
+        for first in 0..choices.len() {
+            for second in first + 1..(first + 8).min(choices.len()) {
+                if second >= choices.len() || choices[first] == 0 {
+                    break;
+                }
+
+                let mut candidate = choices.clone();
+                candidate[second] = candidate[second].saturating_add(candidate[first]);
+                candidate[first] = 0;
+
+                if attempt(candidate, &mut choices, &mut message) {
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved || attempts.get() >= MAX_SHRINK_ATTEMPTS {
+            return (choices, message, shrinks.get());
+        }
+    }
+}
+
+fn find_failure<T: fmt::Debug>(
+    config: &Config,
+    generate: &impl Fn(&mut Source) -> T,
+    property: &impl Fn(&T),
+) -> Option<Failure> {
+    for case in 0..config.cases {
+        let seed = config.seed.wrapping_add(case as u64);
+        let mut source = Source::random(seed);
+
+        if let Err(message) = run_case(&mut source, generate, property) {
+            let original = source.into_choices();
+            let original_choices = original.len();
+            let (choices, message, shrinks) = shrink(original, message, generate, property);
+
+            let value = catch_quietly(|| format!("{:#?}", generate(&mut Source::replay(&choices))))
+                .unwrap_or_else(|error| format!("<generating the value panicked: {error}>"));
+
+            return Some(Failure {
+                case,
+                seed,
+                value,
+                message,
+                original_choices,
+                choices: choices.len(),
+                shrinks,
+            });
+        }
+    }
+
+    None
+}
+
+/// Checks that `property` holds, that is does not panic, for values from `generate`, shrinking
+/// the first failure to a minimal one before panicking with it.
+///
+/// ```
+/// aoc_common::check(
+///     "reversing twice is the identity",
+///     |source| source.vec_of(0..=20, |source| source.u32_in(0..=100)),
+///     |values| {
+///         let mut reversed = values.clone();
+///         reversed.reverse();
+///         reversed.reverse();
+///         assert_eq!(&reversed, values);
+///     },
+/// );
+/// ```
+pub fn check<T: fmt::Debug>(name: &str, generate: impl Fn(&mut Source) -> T, property: impl Fn(&T)) {
+    check_with(&Config::from_env(), name, generate, property);
+}
+
+/// [`check`] with an explicit number of cases and seed.
+pub fn check_with<T: fmt::Debug>(config: &Config, name: &str, generate: impl Fn(&mut Source) -> T, property: impl Fn(&T)) {
+    if let Some(failure) = find_failure(config, &generate, &property) {
+        panic!("property `{name}` {failure}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: Config = Config {
+        cases: 200,
+        seed: 7,
+    };
+
+    fn numbers(source: &mut Source) -> Vec<u32> {
+        source.vec_of(0..=30, |source| source.u32_in(0..=1000))
+    }
+
+    #[test]
+    fn sources_replay_their_choices() {
+        let mut source = Source::random(3);
+        let values = (numbers(&mut source), source.i32_in(-5..=5), source.bool(), *source.choose(&["a", "b", "c"]));
+        let choices = source.into_choices();
+
+        let mut replayed = Source::replay(&choices);
+        let again = (numbers(&mut replayed), replayed.i32_in(-5..=5), replayed.bool(), *replayed.choose(&["a", "b", "c"]));
+
+        assert_eq!(values, again);
+        assert_eq!(replayed.into_choices(), choices);
+
+        let mut empty = Source::replay(&[]);
+        assert_eq!((empty.u64_in(10..=20), empty.i32_in(-5..=5), numbers(&mut empty)), (10, -5, vec![]));
+        assert_eq!(Source::replay(&[u64::MAX]).u64_in(0..=u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn passing_properties_run_every_case() {
+        let cases = Cell::new(0);
+
+        check_with(&CONFIG, "sorting keeps the length", numbers, |values| {
+            cases.set(cases.get() + 1);
+            let mut sorted = values.clone();
+            sorted.sort();
+            assert_eq!(sorted.len(), values.len());
+        });
+
+        assert_eq!(cases.get(), CONFIG.cases);
+    }
+
+    #[test]
+    fn failures_shrink_to_minimal_cases() {
+        let failure = find_failure(&CONFIG, &numbers, &|values: &Vec<u32>| {
+            assert!(values.iter().all(|value| *value < 500), "too large");
+        })
+        .unwrap();
+
+        assert_eq!(failure.value, format!("{:#?}", vec![500]));
+        assert!(failure.message.starts_with("too large at "), "{}", failure.message);
+        assert!(failure.choices < failure.original_choices);
+
+        let failure = find_failure(&CONFIG, &numbers, &|values: &Vec<u32>| {
+            assert!(values.iter().sum::<u32>() < 1500);
+        })
+        .unwrap();
+
+        // Two numbers that only just reach the sum; which two depends on where shrinking started.
+        let values: Vec<u32> = failure
+            .value
+            .split(|char: char| !char.is_ascii_digit())
+            .filter_map(|number| number.parse().ok())
+            .collect();
+        assert_eq!((values.len(), values.iter().sum::<u32>()), (2, 1500));
+
+        let text = failure.to_string();
+        assert!(text.contains(&format!("rerun it alone with {SEED_VARIABLE}={} {CASES_VARIABLE}=1", failure.seed)));
+    }
+
+    #[test]
+    fn failing_checks_panic_with_the_case() {
+        let error = catch_quietly(|| {
+            check_with(&CONFIG, "no sevens", |source| source.u32_in(0..=100), |value| assert_ne!(*value, 7));
+        })
+        .unwrap_err();
+
+        assert!(error.starts_with("property `no sevens` failed on case "), "{error}");
+        assert!(error.contains("\n7\n"), "{error}");
+    }
+}
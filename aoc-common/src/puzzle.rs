@@ -0,0 +1,240 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{Answer, Generator, ParseError, Part, Solution};
+
+pub const REGISTRY_FILE: &str = "registry.rs";
+
+/// Object-safe view of a [`Solution`], so days can be stored side by side in a registry.
+pub trait Puzzle: Sync {
+    fn get_day(&self) -> u8;
+
+    fn get_title(&self) -> &'static str;
+
+    /// Parses the input, with any error already located within it.
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError>;
+
+    /// What `query` takes, for days with a [`Solution::get_explorer`].
+    fn get_query_usage(&self) -> Option<&'static str>;
+
+    /// How `aoc generate` builds inputs, for days with a [`Solution::get_generator`].
+    fn get_generator(&self) -> Option<Generator>;
+}
+
+/// A day's input after parsing, ready to be solved.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Answer;
+
+    /// A summary of the input, for days with a [`Solution::get_explorer`].
+    fn inspect(&self) -> Option<String>;
+
+    /// Answers a query about the input, for days with a [`Solution::get_explorer`].
+    fn query(&self, arguments: &[&str]) -> Option<Result<String, String>>;
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> Answer {
+        S::solve(&self.input, part)
+    }
+
+    fn inspect(&self) -> Option<String> {
+        S::get_explorer().map(|explorer| (explorer.inspect)(&self.input))
+    }
+
+    fn query(&self, arguments: &[&str]) -> Option<Result<String, String>> {
+        S::get_explorer().map(|explorer| (explorer.query)(&self.input, arguments))
+    }
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync + 'static,
+    S::Input: 'static,
+{
+    fn get_day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn get_title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        let input = S::parse(input).map_err(|error| error.locate(input))?;
+
+        Ok(Box::new(ParsedInput::<S> {
+            input,
+        }))
+    }
+
+    fn get_query_usage(&self) -> Option<&'static str> {
+        S::get_explorer().map(|explorer| explorer.usage)
+    }
+
+    fn get_generator(&self) -> Option<Generator> {
+        S::get_generator()
+    }
+}
+
+/// Collection of all known days, ordered by day number.
+pub struct Registry {
+    puzzles: Vec<&'static dyn Puzzle>,
+}
+
+impl Registry {
+    pub fn new(puzzles: &[&'static dyn Puzzle]) -> Self {
+        let mut puzzles = puzzles.to_vec();
+        puzzles.sort_by_key(|puzzle| puzzle.get_day());
+        puzzles.dedup_by_key(|puzzle| puzzle.get_day());
+
+        Registry {
+            puzzles,
+        }
+    }
+
+    pub fn get(&self, day: u8) -> Option<&'static dyn Puzzle> {
+        self.puzzles
+            .iter()
+            .find(|puzzle| puzzle.get_day() == day)
+            .copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Puzzle> + '_ {
+        self.puzzles.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+}
+
+/// Build script entry point for the crate that gathers every day.
+///
+/// Writes `$OUT_DIR/registry.rs`, re-exporting each `dayNN` dependency of the crate's
+/// `Cargo.toml` and listing its `DayNN` in `PUZZLES`, so that a day plugs in by being a
+/// dependency. The crate includes it with `include!(concat!(env!("OUT_DIR"), "/registry.rs"))`.
+pub fn generate_registry() {
+    let manifest_path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set")).join("Cargo.toml");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+
+    let manifest = fs::read_to_string(&manifest_path).unwrap_or_else(|error| panic!("{}: {error}", manifest_path.display()));
+
+    fs::write(out_dir.join(REGISTRY_FILE), get_registry_code(&manifest)).expect("could not write the registry");
+}
+
+/// The days among the `[dependencies]` of a manifest, in order.
+pub fn get_day_dependencies(manifest: &str) -> Vec<u8> {
+    let mut days: Vec<u8> = manifest
+        .lines()
+        .skip_while(|line| line.trim() != "[dependencies]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .filter_map(|line| {
+            let (name, _) = line.split_once('=')?;
+            let day = name.trim().strip_prefix("day")?;
+            day.parse::<u8>().ok().filter(|_| day.len() == 2)
+        })
+        .collect();
+
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+/// The code of `registry.rs` for the days a manifest depends on.
+pub fn get_registry_code(manifest: &str) -> String {
+    let days = get_day_dependencies(manifest);
+    let mut code = String::new();
+
+    for day in &days {
+        let _ = writeln!(code, "pub use day{day:02};");
+    }
+
+    let _ = writeln!(code, "\nconst PUZZLES: [&dyn aoc_common::Puzzle; {}] = [", days.len());
+
+    for day in &days {
+        let _ = writeln!(code, "    &day{day:02}::Day{day:02},");
+    }
+
+    code.push_str("];\n");
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_number;
+
+    struct Count<const DAY: u8>;
+
+    impl<const DAY: u8> Solution for Count<DAY> {
+        const DAY: u8 = DAY;
+        const TITLE: &'static str = "Count";
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split_whitespace().map(parse_number).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            input.iter().sum::<u32>().into()
+        }
+    }
+
+    #[test]
+    fn parse_and_solve() {
+        let puzzle: &dyn Puzzle = &Count::<1>;
+        let parsed = puzzle.parse("1 2 3").unwrap();
+
+        assert_eq!(parsed.solve(Part::One), Answer::Unsigned(3));
+        assert_eq!(parsed.solve(Part::Two), Answer::Unsigned(6));
+    }
+
+    #[test]
+    fn parse_error_is_located() {
+        let puzzle: &dyn Puzzle = &Count::<1>;
+        let error = puzzle.parse("1 2\n3 x").err().unwrap();
+
+        assert_eq!(error.get_location().map(|location| (location.line, location.column)), Some((2, 3)));
+    }
+
+    #[test]
+    fn registry_is_ordered_by_day() {
+        let registry = Registry::new(&[&Count::<3>, &Count::<1>, &Count::<2>, &Count::<1>]);
+
+        let days: Vec<u8> = registry.iter().map(|puzzle| puzzle.get_day()).collect();
+
+        assert_eq!(days, vec![1, 2, 3]);
+        assert!(registry.get(2).is_some());
+        assert!(registry.get(4).is_none());
+    }
+
+    #[test]
+    fn registry_from_dependencies() {
+        let manifest = "[package]\nname = \"aoc2023\"\n\n[dependencies]\naoc-common = { path = \"../aoc-common\" }\n\
+                        day10 = { path = \"../day10\" }\nday02 = { path = \"../day02\" }\nday3 = \"1\"\n\n\
+                        [build-dependencies]\nday04 = { path = \"../day04\" }\n";
+
+        assert_eq!(get_day_dependencies(manifest), vec![2, 10]);
+        assert_eq!(
+            get_registry_code(manifest),
+            "pub use day02;\npub use day10;\n\nconst PUZZLES: [&dyn aoc_common::Puzzle; 2] = [\n    &day02::Day02,\n    &day10::Day10,\n];\n"
+        );
+    }
+}
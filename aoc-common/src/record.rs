@@ -0,0 +1,377 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::crypto::{sha256, to_hex};
+use crate::{count_allocations, format_bytes, span, Allocations, Answer, Json, Level, Part, Puzzle};
+
+/// How results are printed: the plain `Puzzle 0: ...` lines, or one record per part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format: {s}, expected text, json or csv")),
+        }
+    }
+}
+
+/// Outcome of a single part of a day, failed when the input did not parse or the part panicked.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub parse_duration: Duration,
+    pub duration: Duration,
+    /// Only counted with `--memory`, in binaries using the [`crate::CountingAllocator`].
+    pub parse_allocations: Option<Allocations>,
+    pub allocations: Option<Allocations>,
+    /// SHA-256 of the input, to tell which input an answer belongs to without including it.
+    pub input_hash: String,
+    pub error: Option<String>,
+}
+
+pub const CSV_HEADER: &str = "day,title,part,answer,type,parse_ns,duration_ns,parse_allocations,parse_bytes,\
+parse_peak_bytes,allocations,bytes,peak_bytes,input_sha256,success,error";
+
+impl Record {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn to_json(&self) -> Json {
+        let answer = match &self.answer {
+            Some(Answer::Signed(value)) => Json::from(*value),
+            Some(Answer::Unsigned(value)) => Json::from(*value),
+            Some(Answer::Text(value)) => Json::from(value.as_str()),
+            Some(Answer::Unsolved(_)) | None => Json::Null,
+        };
+
+        Json::object([
+            ("day", self.day.into()),
+            ("title", self.title.into()),
+            ("part", self.part.get_number().into()),
+            ("answer", answer),
+            ("type", self.answer.as_ref().map(Answer::get_type_name).into()),
+            ("parse_ns", self.parse_duration.as_nanos().into()),
+            ("duration_ns", self.duration.as_nanos().into()),
+            ("parse_allocations", get_allocations_json(self.parse_allocations)),
+            ("allocations", get_allocations_json(self.allocations)),
+            ("input_sha256", self.input_hash.as_str().into()),
+            ("success", self.is_success().into()),
+            ("error", self.error.as_deref().into()),
+        ])
+    }
+
+    pub fn to_csv(&self) -> String {
+        [
+            self.day.to_string(),
+            escape_csv(self.title),
+            self.part.to_string(),
+            self.answer.as_ref().map(|answer| escape_csv(&answer.to_string())).unwrap_or_default(),
+            self.answer.as_ref().map(Answer::get_type_name).unwrap_or_default().to_string(),
+            self.parse_duration.as_nanos().to_string(),
+            self.duration.as_nanos().to_string(),
+            get_allocations_csv(self.parse_allocations),
+            get_allocations_csv(self.allocations),
+            self.input_hash.clone(),
+            self.is_success().to_string(),
+            self.error.as_deref().map(escape_csv).unwrap_or_default(),
+        ]
+        .join(",")
+    }
+}
+
+fn get_allocations_json(allocations: Option<Allocations>) -> Json {
+    match allocations {
+        Some(allocations) => Json::object([
+            ("count", allocations.count.into()),
+            ("bytes", allocations.bytes.into()),
+            ("peak_bytes", allocations.peak_bytes.into()),
+        ]),
+        None => Json::Null,
+    }
+}
+
+fn get_allocations_csv(allocations: Option<Allocations>) -> String {
+    match allocations {
+        Some(allocations) => format!("{},{},{}", allocations.count, allocations.bytes, allocations.peak_bytes),
+        None => ",,".to_string(),
+    }
+}
+
+fn format_allocations(name: &str, allocations: Allocations) -> String {
+    format!(
+        "{name}: {} allocation(s), {} allocated, {} peak\n",
+        allocations.count,
+        format_bytes(allocations.bytes),
+        format_bytes(allocations.peak_bytes)
+    )
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes records in the given format; `Text` keeps the binaries' original `Puzzle 0: ...` lines,
+/// followed by what parsing and each part allocated when that was counted.
+pub fn format_records(records: &[Record], format: Format) -> String {
+    match format {
+        Format::Text => {
+            let mut text: String = records
+                .iter()
+                .filter_map(|record| {
+                    let answer = record.answer.as_ref()?;
+                    Some(format!("Puzzle {}: {answer}\n", record.part.get_number() - 1))
+                })
+                .collect();
+
+            if let Some(allocations) = records.first().and_then(|record| record.parse_allocations) {
+                text.push_str(&format_allocations("parse", allocations));
+            }
+
+            for record in records {
+                if let Some(allocations) = record.allocations {
+                    text.push_str(&format_allocations(&format!("part {}", record.part), allocations));
+                }
+            }
+
+            text
+        }
+        Format::Json => format!("{}\n", Json::Array(records.iter().map(Record::to_json).collect())),
+        Format::Csv => records
+            .iter()
+            .fold(format!("{CSV_HEADER}\n"), |csv, record| csv + &record.to_csv() + "\n"),
+    }
+}
+
+/// Parses and solves the given parts, turning a parse error or a panic into failed records.
+pub fn get_records(puzzle: &dyn Puzzle, input: &str, parts: &[Part], file: impl fmt::Display) -> Vec<Record> {
+    let input_hash = to_hex(&sha256(&[input.as_bytes()]));
+    let start = Instant::now();
+    let span = span!(Level::Info, "day {} parse", puzzle.get_day());
+    let (parsed, parse_allocations) = count_allocations(|| panic::catch_unwind(AssertUnwindSafe(|| puzzle.parse(input))));
+    drop(span);
+    let parse_duration = start.elapsed();
+
+    let record = |part, answer, duration, allocations, error| Record {
+        day: puzzle.get_day(),
+        title: puzzle.get_title(),
+        part,
+        answer,
+        parse_duration,
+        duration,
+        parse_allocations,
+        allocations,
+        input_hash: input_hash.clone(),
+        error,
+    };
+
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(error)) => {
+            let error = error.with_file(&file).to_string();
+            return parts
+                .iter()
+                .map(|&part| record(part, None, Duration::ZERO, None, Some(error.clone())))
+                .collect();
+        }
+        Err(payload) => {
+            let error = format!("{file}: parsing panicked: {}", get_panic_message(&*payload));
+            return parts
+                .iter()
+                .map(|&part| record(part, None, Duration::ZERO, None, Some(error.clone())))
+                .collect();
+        }
+    };
+
+    parts
+        .iter()
+        .map(|&part| {
+            let _span = span!(Level::Info, "day {} part {part}", puzzle.get_day());
+            let start = Instant::now();
+            let (answer, allocations) = count_allocations(|| panic::catch_unwind(AssertUnwindSafe(|| parsed.solve(part))));
+            let duration = start.elapsed();
+
+            match answer {
+                Ok(Answer::Unsolved(reason)) => record(part, None, duration, allocations, Some(format!("part {part}: {reason}"))),
+                Ok(answer) => record(part, Some(answer), duration, allocations, None),
+                Err(payload) => {
+                    let error = format!("part {part} panicked: {}", get_panic_message(&*payload));
+                    record(part, None, duration, allocations, Some(error))
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn get_panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_number, ParseError, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 4;
+        const TITLE: &'static str = "Sum, \"Quoted\"";
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split_whitespace().map(parse_number).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.iter().sum::<u32>().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            input.iter().map(|value| 100 / value).sum::<u32>().into()
+        }
+    }
+
+    /// Solves part one only.
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        const DAY: u8 = 7;
+        const TITLE: &'static str = "Unfinished";
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input.split_whitespace().map(parse_number).collect()
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part_two(_: &Self::Input) -> Answer {
+            Answer::Unsolved("not solved yet".to_string())
+        }
+    }
+
+    /// The allocations test turns counting on for the whole test binary at some point.
+    fn get_uncounted_records(input: &str) -> Vec<Record> {
+        get_records(&Sum, input, &Part::ALL, "sum.txt")
+            .into_iter()
+            .map(|record| Record {
+                parse_allocations: None,
+                allocations: None,
+                ..record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unsolved_parts_fail() {
+        let records = get_records(&Unfinished, "1 2", &Part::ALL, "unfinished.txt");
+
+        assert_eq!(records[0].answer, Some(Answer::Unsigned(2)));
+        assert!(records[0].is_success());
+        assert_eq!(records[1].answer, None);
+        assert_eq!(records[1].error.as_deref(), Some("part 2: not solved yet"));
+    }
+
+    #[test]
+    fn records() {
+        let records = get_uncounted_records("1 2");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].answer, Some(Answer::Unsigned(3)));
+        assert_eq!(records[1].answer, Some(Answer::Unsigned(150)));
+        assert!(records.iter().all(Record::is_success));
+        assert_eq!(records[0].input_hash, to_hex(&sha256(&[b"1 2"])));
+
+        assert_eq!(format_records(&records, Format::Text), "Puzzle 0: 3\nPuzzle 1: 150\n");
+
+        let json = format_records(&records, Format::Json);
+        assert!(json.starts_with("[{\"day\":4,\"title\":\"Sum, \\\"Quoted\\\"\",\"part\":1,\"answer\":3,\"type\":\"unsigned\","));
+        assert!(json.contains("\"success\":true,\"error\":null}"));
+
+        let csv = format_records(&records, Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("4,\"Sum, \"\"Quoted\"\"\",1,3,unsigned,"));
+        assert!(lines[2].ends_with(&format!(",{},true,", records[1].input_hash)));
+    }
+
+    #[test]
+    fn counted_allocations() {
+        let allocations = Allocations {
+            count: 3,
+            bytes: 2048,
+            peak_bytes: 1024,
+        };
+        let records: Vec<Record> = get_uncounted_records("1 2")
+            .into_iter()
+            .map(|record| Record {
+                parse_allocations: Some(allocations),
+                allocations: (record.part == Part::Two).then_some(allocations),
+                ..record
+            })
+            .collect();
+
+        assert_eq!(
+            format_records(&records, Format::Text),
+            "Puzzle 0: 3\nPuzzle 1: 150\nparse: 3 allocation(s), 2.0 KiB allocated, 1.0 KiB peak\n\
+             part 2: 3 allocation(s), 2.0 KiB allocated, 1.0 KiB peak\n"
+        );
+        assert!(records[1].to_json().to_string().contains(
+            "\"parse_allocations\":{\"count\":3,\"bytes\":2048,\"peak_bytes\":1024},\"allocations\":{\"count\":3,"
+        ));
+        assert!(records[0].to_csv().contains(",3,2048,1024,,,,"));
+    }
+
+    #[test]
+    fn failures() {
+        let records = get_uncounted_records("1 x");
+
+        assert!(records.iter().all(|record| !record.is_success() && record.answer.is_none()));
+        assert!(records[0].error.as_deref().unwrap().starts_with("sum.txt:1:3"));
+        assert_eq!(format_records(&records, Format::Text), "");
+        assert!(format_records(&records, Format::Csv).contains(",false,\"sum.txt:1:3: invalid number\n"));
+
+        let records = get_uncounted_records("0");
+
+        assert!(records[0].is_success());
+        assert_eq!(records[1].error.as_deref(), Some("part 2 panicked: attempt to divide by zero"));
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
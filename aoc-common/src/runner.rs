@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use crate::{span, Answer, Level, ParseError, Part, Puzzle};
+
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Answer,
+    pub duration: Duration,
+}
+
+/// Answers and wall-clock timings of a single run of a day.
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    pub parse_duration: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    pub fn get_total_duration(&self) -> Duration {
+        self.parts
+            .iter()
+            .map(|part| part.duration)
+            .fold(self.parse_duration, |total, duration| total + duration)
+    }
+}
+
+pub fn run(puzzle: &dyn Puzzle, input: &str, parts: &[Part]) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let span = span!(Level::Info, "day {} parse", puzzle.get_day());
+    let parsed = puzzle.parse(input)?;
+    drop(span);
+    let parse_duration = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let _span = span!(Level::Info, "day {} part {part}", puzzle.get_day());
+            let start = Instant::now();
+            let answer = parsed.solve(part);
+
+            PartReport {
+                part,
+                answer,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Report {
+        day: puzzle.get_day(),
+        title: puzzle.get_title(),
+        parse_duration,
+        parts,
+    })
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{nanos}ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    struct Words;
+
+    impl Solution for Words {
+        const DAY: u8 = 9;
+        const TITLE: &'static str = "Words";
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.split_whitespace().map(|word| word.to_string()).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            input.join("-").into()
+        }
+    }
+
+    #[test]
+    fn run_selected_parts() {
+        let report = run(&Words, "a b", &[Part::Two]).unwrap();
+
+        assert_eq!(report.day, 9);
+        assert_eq!(report.title, "Words");
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, Part::Two);
+        assert_eq!(report.parts[0].answer, Answer::Text("a-b".to_string()));
+        assert!(report.get_total_duration() >= report.parse_duration);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50s");
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::{Answer, Explorer, Generator, ParseError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn get_number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(value),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_number())
+    }
+}
+
+/// A single day of the calendar: how to parse its input and solve both parts.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(input: &Self::Input) -> Answer;
+
+    fn part_two(input: &Self::Input) -> Answer;
+
+    fn solve(input: &Self::Input, part: Part) -> Answer {
+        match part {
+            Part::One => Self::part_one(input),
+            Part::Two => Self::part_two(input),
+        }
+    }
+
+    /// How `aoc repl` inspects and queries the parsed input, `None` for days that have no queries.
+    fn get_explorer() -> Option<Explorer<Self>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// How `aoc generate` builds inputs with known answers, `None` for days without a generator.
+    fn get_generator() -> Option<Generator> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Lines";
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.lines().map(|line| line.to_string()).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            input.concat().into()
+        }
+    }
+
+    #[test]
+    fn solve() {
+        let input = Lines::parse("a\nb\nc").unwrap();
+
+        assert_eq!(Lines::solve(&input, Part::One), Answer::Unsigned(3));
+        assert_eq!(Lines::solve(&input, Part::Two), Answer::Text("abc".to_string()));
+    }
+
+    #[test]
+    fn part_from_number() {
+        assert_eq!(Part::try_from(1), Ok(Part::One));
+        assert_eq!(Part::try_from(2), Ok(Part::Two));
+        assert_eq!(Part::try_from(3), Err(3));
+    }
+}
@@ -0,0 +1,197 @@
+use std::cell::Cell;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::{format_duration, Args};
+
+pub const LOG_VARIABLE: &str = "AOC_LOG";
+
+/// How much the solvers report on stderr, `Off` unless `-v`, `-vv` or `$AOC_LOG` asks for more.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    #[default]
+    Off,
+    /// Phases: parsing and each part, with their timings.
+    Info,
+    /// Progress within a day's solver.
+    Debug,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "0" | "off" => Ok(Level::Off),
+            "1" | "info" => Ok(Level::Info),
+            "2" | "debug" => Ok(Level::Debug),
+            _ => Err(format!("invalid {LOG_VARIABLE}: {s}, expected off, info or debug")),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Off => "OFF",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        };
+
+        f.pad(name)
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static START: OnceLock<Instant> = OnceLock::new();
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+pub fn set_level(level: Level) {
+    START.get_or_init(Instant::now);
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether events at `level` are written. The macros check it before formatting anything,
+/// so a disabled event costs a single relaxed load.
+#[inline]
+pub fn is_enabled(level: Level) -> bool {
+    level != Level::Off && LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Sets the level from `-v` (info) or `-vv` (debug), falling back to `$AOC_LOG`.
+pub fn init_tracing(args: &mut Args) -> Result<(), String> {
+    let level = if args.flag("-vv") {
+        Level::Debug
+    } else if args.flag("-v") {
+        Level::Info
+    } else {
+        match env::var(LOG_VARIABLE) {
+            Ok(value) => value.parse()?,
+            Err(_) => Level::Off,
+        }
+    };
+
+    set_level(level);
+    Ok(())
+}
+
+fn format_event(elapsed: f64, level: Level, depth: usize, message: fmt::Arguments) -> String {
+    format!("[{elapsed:>9.3}s] {level:<5} {}{message}", "  ".repeat(depth))
+}
+
+/// Writes an event to stderr, indented by the spans it is in. Use [`info!`](crate::info) and
+/// [`debug!`](crate::debug) rather than calling it directly.
+pub fn write_event(level: Level, message: fmt::Arguments) {
+    let elapsed = START.get_or_init(Instant::now).elapsed().as_secs_f64();
+    let depth = DEPTH.with(Cell::get);
+
+    eprintln!("{}", format_event(elapsed, level, depth, message));
+}
+
+/// A timed phase: written when entered and, with its duration, when dropped. Events in
+/// between are indented under it. Create it with [`span!`](crate::span).
+pub struct Span {
+    level: Level,
+    name: String,
+    start: Instant,
+}
+
+impl Span {
+    pub fn enter(level: Level, name: String) -> Self {
+        write_event(level, format_args!("{name}"));
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+
+        Span {
+            level,
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+        write_event(self.level, format_args!("{} done in {}", self.name, format_duration(self.start.elapsed())));
+    }
+}
+
+/// Writes an info event, a phase of a run, when `-v` or more is enabled.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        if $crate::is_enabled($crate::Level::Info) {
+            $crate::write_event($crate::Level::Info, format_args!($($arg)+));
+        }
+    };
+}
+
+/// Writes a debug event, progress within a solver, when `-vv` is enabled.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        if $crate::is_enabled($crate::Level::Debug) {
+            $crate::write_event($crate::Level::Debug, format_args!($($arg)+));
+        }
+    };
+}
+
+/// Enters a [`Span`](crate::Span) at the given level, `None` when that level is disabled.
+/// Keep it alive for the phase: `let _span = span!(Level::Info, "part {part}");`.
+#[macro_export]
+macro_rules! span {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::is_enabled($level) {
+            Some($crate::Span::enter($level, format!($($arg)+)))
+        } else {
+            None
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert_eq!("DEBUG".parse(), Ok(Level::Debug));
+        assert_eq!("1".parse(), Ok(Level::Info));
+        assert_eq!("".parse(), Ok(Level::Off));
+        assert!("trace".parse::<Level>().is_err());
+
+        assert!(Level::Debug > Level::Info);
+        assert!(!is_enabled(Level::Off));
+    }
+
+    #[test]
+    fn events() {
+        assert_eq!(
+            format_event(1.5, Level::Info, 0, format_args!("day {} parse", 5)),
+            "[    1.500s] INFO  day 5 parse"
+        );
+        assert_eq!(
+            format_event(12.25, Level::Debug, 2, format_args!("seed range 1/10")),
+            "[   12.250s] DEBUG     seed range 1/10"
+        );
+    }
+
+    #[test]
+    fn verbosity_flags() {
+        let mut args = Args::new(["-vv".to_string(), "input.txt".to_string()]);
+        init_tracing(&mut args).unwrap();
+
+        assert!(is_enabled(Level::Debug));
+        assert_eq!(args.positional().as_deref(), Some("input.txt"));
+
+        set_level(Level::Off);
+        assert!(!is_enabled(Level::Info));
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    find_examples_dir, get_records, read_source, Answers, InputResolver, InputSource, Part, Puzzle, Record,
+    EXAMPLES_FILE,
+};
+
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A file the watch re-solves: the day's input or one of its examples.
+#[derive(Clone, Debug, PartialEq)]
+struct Target {
+    name: String,
+    path: PathBuf,
+    expected: BTreeMap<Part, String>,
+}
+
+/// Modification time and length of each watched file, `None` while it is missing.
+type Snapshot = BTreeMap<PathBuf, Option<(SystemTime, u64)>>;
+
+fn get_snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| {
+            let metadata = fs::metadata(path).ok();
+            let state = metadata.and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+
+            (path.clone(), state)
+        })
+        .collect()
+}
+
+/// Paths that were added, removed or modified between two snapshots.
+fn get_changed_paths(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let removed = previous.keys().filter(|path| !current.contains_key(*path));
+    let changed = current
+        .iter()
+        .filter(|(path, state)| previous.get(*path) != Some(state))
+        .map(|(path, _)| path);
+
+    let mut paths: Vec<PathBuf> = changed.chain(removed).cloned().collect();
+    paths.sort();
+    paths
+}
+
+/// The day's input, when it resolves to a file, and every `examples/*.txt`, with expected
+/// answers from `answers.toml` and `examples/expected.toml` where they are known.
+fn get_targets(day: u8, resolver: &InputResolver, explicit: Option<&str>, examples_dir: Option<&Path>) -> Vec<Target> {
+    let mut targets = vec![];
+
+    if let Ok(InputSource::Path(path)) = resolver.resolve(day, explicit) {
+        // Answers in answers.toml belong to the profile's input, not to one given on the command line.
+        let (name, expected) = match explicit {
+            Some(_) => ("input".to_string(), BTreeMap::new()),
+            None => {
+                let expected = Answers::find()
+                    .ok()
+                    .and_then(|answers| answers.get(day, resolver.get_profile()).map(|expectation| expectation.answers.clone()))
+                    .unwrap_or_default();
+
+                (resolver.get_profile().to_string(), expected)
+            }
+        };
+
+        targets.push(Target {
+            name,
+            path,
+            expected,
+        });
+    }
+
+    let Some(examples_dir) = examples_dir else {
+        return targets;
+    };
+
+    let answers = Answers::load(&examples_dir.join(EXAMPLES_FILE)).unwrap_or_default();
+    let mut examples: Vec<PathBuf> = fs::read_dir(examples_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    examples.sort();
+
+    for path in examples {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let expected = answers.get(day, &name).map(|expectation| expectation.answers.clone()).unwrap_or_default();
+
+        targets.push(Target {
+            name,
+            path,
+            expected,
+        });
+    }
+
+    targets
+}
+
+/// One line per part: the answer, what it was on the previous run if it changed, and the
+/// expected answer if it does not match. Errors follow the lines, once each.
+fn describe(target: &Target, records: &[Record], previous: Option<&[Record]>) -> String {
+    let mut lines = String::new();
+    let mut errors: Vec<&str> = vec![];
+
+    for record in records {
+        let part = record.part;
+        let actual = record.answer.as_ref().map(|answer| answer.to_string());
+
+        match &actual {
+            Some(actual) => lines.push_str(&format!("{} part {part}: {actual}", target.name)),
+            None => lines.push_str(&format!("{} part {part}: error", target.name)),
+        }
+
+        if let Some(error) = record.error.as_deref().filter(|error| !errors.contains(error)) {
+            errors.push(error);
+        }
+
+        let before = previous.and_then(|previous| previous.iter().find(|before| before.part == part));
+        if let Some(before) = before {
+            let before_answer = before.answer.as_ref().map(|answer| answer.to_string());
+            if before_answer != actual {
+                lines.push_str(&format!(" (was {})", before_answer.as_deref().unwrap_or("an error")));
+            }
+        }
+
+        if let (Some(actual), Some(expected)) = (&actual, target.expected.get(&part)) {
+            if actual != expected {
+                lines.push_str(&format!(" [expected {expected}]"));
+            }
+        }
+
+        lines.push('\n');
+    }
+
+    for error in errors {
+        lines.push_str(&format!("error: {error}\n"));
+    }
+
+    lines
+}
+
+fn solve(puzzle: &dyn Puzzle, target: &Target) -> Vec<Record> {
+    let source = InputSource::Path(target.path.clone());
+
+    match read_source(&source) {
+        Ok(text) => get_records(puzzle, &text, &Part::ALL, &source),
+        Err(error) => Part::ALL
+            .into_iter()
+            .map(|part| Record {
+                day: puzzle.get_day(),
+                title: puzzle.get_title(),
+                part,
+                answer: None,
+                parse_duration: Duration::ZERO,
+                duration: Duration::ZERO,
+                parse_allocations: None,
+                allocations: None,
+                input_hash: String::new(),
+                error: Some(error.to_string()),
+            })
+            .collect(),
+    }
+}
+
+/// Polls the day's input and examples every `interval`, re-solving all of them whenever any
+/// changes. Polling rather than file system events keeps it working in any container.
+pub fn watch(puzzle: &dyn Puzzle, resolver: &InputResolver, explicit: Option<&str>, interval: Duration) -> ! {
+    let day = puzzle.get_day();
+    let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let examples_dir = find_examples_dir(&current_dir, day);
+
+    let mut snapshot: Option<Snapshot> = None;
+    let mut previous: BTreeMap<String, Vec<Record>> = BTreeMap::new();
+    let mut round = 0;
+
+    loop {
+        let targets = get_targets(day, resolver, explicit, examples_dir.as_deref());
+
+        let mut paths: Vec<PathBuf> = targets.iter().map(|target| target.path.clone()).collect();
+        if let Some(examples_dir) = &examples_dir {
+            paths.push(examples_dir.join(EXAMPLES_FILE));
+        }
+        let current = get_snapshot(&paths);
+
+        if snapshot.as_ref() != Some(&current) {
+            round += 1;
+
+            match &snapshot {
+                None => println!("--- day {day}: watching {} file(s) ---", current.len()),
+                Some(snapshot) => {
+                    let changed: Vec<String> = get_changed_paths(snapshot, &current)
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect();
+                    println!("--- run {round}: {} changed ---", changed.join(", "));
+                }
+            }
+
+            if targets.is_empty() {
+                println!("no input or examples for day {day} yet");
+            }
+
+            for target in &targets {
+                let records = solve(puzzle, target);
+                print!("{}", describe(target, &records, previous.get(&target.name).map(Vec::as_slice)));
+                previous.insert(target.name.clone(), records);
+            }
+
+            snapshot = Some(current);
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    fn create_record(part: Part, answer: Option<u64>) -> Record {
+        Record {
+            day: 5,
+            title: "Watched",
+            part,
+            answer: answer.map(Answer::Unsigned),
+            parse_duration: Duration::ZERO,
+            duration: Duration::ZERO,
+            parse_allocations: None,
+            allocations: None,
+            input_hash: String::new(),
+            error: answer.is_none().then(|| "invalid number".to_string()),
+        }
+    }
+
+    #[test]
+    fn changes_are_polled() {
+        let dir = env::temp_dir().join(format!("aoc-common-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("day05.txt");
+        let example = dir.join("example_0.txt");
+        fs::write(&input, "1 2").unwrap();
+
+        let paths = [input.clone(), example.clone()];
+        let before = get_snapshot(&paths);
+        assert_eq!(get_changed_paths(&before, &get_snapshot(&paths)), Vec::<PathBuf>::new());
+
+        fs::write(&input, "1 2 3").unwrap();
+        fs::write(&example, "4").unwrap();
+        assert_eq!(get_changed_paths(&before, &get_snapshot(&paths)), vec![input, example]);
+        assert_eq!(get_changed_paths(&before, &get_snapshot(&paths[..1])), paths.to_vec());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn targets() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let resolver = InputResolver::new(None, env::temp_dir());
+        let examples_dir = workspace.join("day05").join("examples");
+
+        let targets = get_targets(5, &resolver, Some("input.txt"), Some(&examples_dir));
+
+        assert_eq!(targets[0].name, "input");
+        assert_eq!(targets[0].path, PathBuf::from("input.txt"));
+        assert!(targets[0].expected.is_empty());
+        assert_eq!(targets[1].name, "example_0");
+        assert_eq!(targets[1].expected.get(&Part::One).map(String::as_str), Some("35"));
+        assert!(get_targets(5, &resolver, Some("-"), None).is_empty());
+    }
+
+    #[test]
+    fn answers_are_compared_with_the_previous_run() {
+        let target = Target {
+            name: "example_0".to_string(),
+            path: PathBuf::from("example_0.txt"),
+            expected: BTreeMap::from([(Part::One, "35".to_string()), (Part::Two, "46".to_string())]),
+        };
+
+        let first = vec![create_record(Part::One, Some(35)), create_record(Part::Two, None)];
+        assert_eq!(
+            describe(&target, &first, None),
+            "example_0 part 1: 35\nexample_0 part 2: error\nerror: invalid number\n"
+        );
+
+        let second = vec![create_record(Part::One, Some(35)), create_record(Part::Two, Some(45))];
+        assert_eq!(
+            describe(&target, &second, Some(&first)),
+            "example_0 part 1: 35\nexample_0 part 2: 45 (was an error) [expected 46]\n"
+        );
+    }
+}
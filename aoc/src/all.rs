@@ -0,0 +1,239 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{
+    format_duration, get_records, read_source, Args, InputError, InputResolver, Part, Puzzle, Record, Registry,
+};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+use crate::run::{get_part, get_resolver};
+use crate::table::{Align, Table};
+
+pub const USAGE: &str = "aoc all [--part <1|2>] [--profile <name>] [--jobs <n>]";
+
+/// Everything one day reported, or why it could not run.
+pub struct Outcome {
+    pub day: u8,
+    pub title: &'static str,
+    pub result: std::result::Result<Vec<Record>, InputError>,
+    pub duration: Duration,
+}
+
+impl Outcome {
+    /// Days without an input, or with only an encrypted one and no key, are skipped rather
+    /// than failed, a profile may not have them all. Any other input error is a failure.
+    pub fn is_skipped(&self) -> bool {
+        self.result
+            .as_ref()
+            .is_err_and(|error| matches!(error, InputError::NotFound { .. }) || error.is_missing_key())
+    }
+
+    pub fn is_failure(&self) -> bool {
+        match &self.result {
+            Ok(records) => !records.iter().all(Record::is_success),
+            Err(_) => !self.is_skipped(),
+        }
+    }
+}
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let part = get_part(&mut args)?;
+    let resolver = get_resolver(&mut args)?;
+    let jobs = match args.parsed_option::<usize>("--jobs")? {
+        Some(0) => return Err(Error::Usage("--jobs must be at least 1".to_string())),
+        Some(jobs) => jobs,
+        None => thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+    };
+    args.finish()?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let registry = get_registry();
+
+    let start = Instant::now();
+    let outcomes = run_all(&registry, &resolver, &parts, jobs);
+    let elapsed = start.elapsed();
+
+    print!("{}", create_table(&outcomes, &parts));
+
+    let total: Duration = outcomes.iter().map(|outcome| outcome.duration).sum();
+    println!(
+        "{} day(s) in {} on {} thread(s), {} if run one after another",
+        outcomes.len(),
+        format_duration(elapsed),
+        jobs.min(outcomes.len()).max(1),
+        format_duration(total)
+    );
+
+    for outcome in &outcomes {
+        let mut errors: Vec<String> = match &outcome.result {
+            Ok(records) => records.iter().filter_map(|record| record.error.clone()).collect(),
+            Err(error) => vec![error.to_string()],
+        };
+        errors.dedup();
+
+        for error in errors {
+            eprintln!("day {}: {error}", outcome.day);
+        }
+    }
+
+    let failures = outcomes.iter().filter(|outcome| outcome.is_failure()).count();
+    if failures > 0 {
+        return Err(Error::Failed(failures));
+    }
+
+    Ok(())
+}
+
+/// Runs every registered day on `jobs` worker threads, each taking the next day from a shared
+/// counter, and returns the outcomes ordered by day. Panics are caught per day, see [`get_records`].
+pub fn run_all(registry: &Registry, resolver: &InputResolver, parts: &[Part], jobs: usize) -> Vec<Outcome> {
+    let puzzles: Vec<&dyn Puzzle> = registry.iter().collect();
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            scope.spawn(|| {
+                while let Some(&puzzle) = puzzles.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let outcome = run_day(puzzle, resolver, parts);
+                    outcomes.lock().unwrap().push(outcome);
+                }
+            });
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by_key(|outcome| outcome.day);
+    outcomes
+}
+
+fn run_day(puzzle: &dyn Puzzle, resolver: &InputResolver, parts: &[Part]) -> Outcome {
+    let start = Instant::now();
+
+    let result = resolver
+        .resolve(puzzle.get_day(), None)
+        .and_then(|source| Ok((read_source(&source)?, source)))
+        .map(|(text, source)| get_records(puzzle, &text, parts, source));
+
+    Outcome {
+        day: puzzle.get_day(),
+        title: puzzle.get_title(),
+        result,
+        duration: start.elapsed(),
+    }
+}
+
+fn create_table(outcomes: &[Outcome], parts: &[Part]) -> Table {
+    let mut columns = vec![("Day", Align::Right), ("Title", Align::Left)];
+    for part in parts {
+        columns.push((if *part == Part::One { "Part 1" } else { "Part 2" }, Align::Left));
+    }
+    columns.extend([("Time", Align::Right), ("Status", Align::Left)]);
+
+    let mut table = Table::new(&columns);
+
+    for outcome in outcomes {
+        let mut row = vec![outcome.day.to_string(), outcome.title.to_string()];
+
+        let status = match &outcome.result {
+            Ok(records) => {
+                row.extend(records.iter().map(|record| match &record.answer {
+                    Some(answer) => answer.to_string(),
+                    None => "-".to_string(),
+                }));
+
+                if outcome.is_failure() { "FAILED" } else { "ok" }
+            }
+            Err(error) => {
+                row.extend(parts.iter().map(|_| "-".to_string()));
+
+                if error.is_missing_key() {
+                    "no key"
+                } else if outcome.is_skipped() {
+                    "no input"
+                } else {
+                    "FAILED"
+                }
+            }
+        };
+
+        row.extend([format_duration(outcome.duration), status.to_string()]);
+        table.push(row);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use aoc_common::{Answer, ParseError, Solution};
+
+    use crate::testing::create_temp_dir;
+
+    struct Lines<const DAY: u8>;
+
+    impl<const DAY: u8> Solution for Lines<DAY> {
+        const DAY: u8 = DAY;
+        const TITLE: &'static str = "Lines";
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> std::result::Result<Self::Input, ParseError> {
+            Ok(input.lines().map(|line| line.to_string()).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            assert!(input.len() < 3, "too many lines");
+            input.concat().into()
+        }
+    }
+
+    #[test]
+    fn days_run_concurrently_in_order() {
+        let dir = create_temp_dir("all");
+        fs::write(dir.join("day01.txt"), "a\nb").unwrap();
+        fs::write(dir.join("day02.txt"), "a\nb\nc").unwrap();
+        fs::write(dir.join("day03.txt"), "c").unwrap();
+        fs::write(dir.join("day05.txt"), [0xff, 0xfe]).unwrap();
+
+        let registry = Registry::new(&[&Lines::<3>, &Lines::<1>, &Lines::<2>, &Lines::<4>, &Lines::<5>]);
+        let resolver = InputResolver::new(Some(dir.clone()), dir.clone());
+
+        let outcomes = run_all(&registry, &resolver, &Part::ALL, 3);
+
+        let days: Vec<u8> = outcomes.iter().map(|outcome| outcome.day).collect();
+        assert_eq!(days, vec![1, 2, 3, 4, 5]);
+
+        // A missing input is skipped, but one that cannot be read is a failure.
+        let failures: Vec<bool> = outcomes.iter().map(Outcome::is_failure).collect();
+        assert_eq!(failures, vec![false, true, false, false, true]);
+        assert!(outcomes[3].is_skipped() && !outcomes[4].is_skipped());
+
+        let table = create_table(&outcomes, &Part::ALL).to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[2].starts_with("  1  Lines  2       ab") && lines[2].ends_with("ok"));
+        assert!(lines[3].starts_with("  2  Lines  3       -") && lines[3].ends_with("FAILED"));
+        assert!(lines[5].ends_with("no input"));
+        assert!(lines[6].ends_with("FAILED"));
+
+        let records = outcomes[1].result.as_ref().unwrap();
+        assert_eq!(records[0].answer, Some(Answer::Unsigned(3)));
+        assert_eq!(records[1].error.as_deref(), Some("part 2 panicked: too many lines"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
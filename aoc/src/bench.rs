@@ -0,0 +1,360 @@
+use std::fmt;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use aoc_common::{format_duration, read_source, Answers, Args, Json, ParseError, Part, Puzzle, PUZZLE_INPUT};
+use aoc2023::get_registry;
+
+use crate::client::get_unix_time;
+use crate::error::{Error, Result};
+use crate::history::{check_regressions, get_git_revision, get_threshold, History};
+use crate::run::{get_part, get_resolver};
+use crate::table::{Align, Table};
+
+pub const USAGE: &str = "aoc bench [<day>] [--part <1|2>] [--input <path | ->] [--profile <name>] [--warmup <runs>] [--iterations <runs>]
+          [--max-time <seconds>] [--slow] [--format <table|json>] [--record [--threshold <percent>]]";
+
+const DEFAULT_WARMUP: usize = 3;
+const DEFAULT_ITERATIONS: usize = 100;
+const DEFAULT_MAX_TIME: f64 = 5.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "parse" => Ok(Stage::Parse),
+            _ => value
+                .parse::<u8>()
+                .ok()
+                .and_then(|part| Part::try_from(part).ok())
+                .map(Stage::Part)
+                .ok_or_else(|| format!("unknown stage: {value}")),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+/// How long to measure each stage for.
+#[derive(Clone, Copy, Debug)]
+pub struct Settings {
+    pub warmup: usize,
+    pub iterations: usize,
+    /// Stops sampling a stage early once this much time was spent on it; at least one sample is always taken.
+    pub max_time: Duration,
+}
+
+impl Settings {
+    pub fn from_args(args: &mut Args) -> Result<Self> {
+        let warmup = args.parsed_option::<usize>("--warmup")?.unwrap_or(DEFAULT_WARMUP);
+        let iterations = args.parsed_option::<usize>("--iterations")?.unwrap_or(DEFAULT_ITERATIONS);
+        let max_time = args.parsed_option::<f64>("--max-time")?.unwrap_or(DEFAULT_MAX_TIME);
+
+        if iterations == 0 {
+            return Err(Error::Usage("--iterations must be at least 1".to_string()));
+        }
+
+        let max_time = Duration::try_from_secs_f64(max_time)
+            .map_err(|_| Error::Usage(format!("invalid --max-time: {max_time}")))?;
+
+        Ok(Settings {
+            warmup,
+            iterations,
+            max_time,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|sample| sample.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / count as f64;
+        let variance = if count > 1 {
+            nanos.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+
+        Stats {
+            iterations: count,
+            mean: Duration::from_nanos(mean.round() as u64),
+            median,
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            min: sorted[0],
+            max: sorted[count - 1],
+        }
+    }
+}
+
+pub struct Measurement {
+    pub day: u8,
+    pub title: &'static str,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+impl Measurement {
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("day", self.day.into()),
+            ("title", self.title.into()),
+            ("stage", self.stage.to_string().into()),
+            ("iterations", self.stats.iterations.into()),
+            ("mean_ns", self.stats.mean.as_nanos().into()),
+            ("median_ns", self.stats.median.as_nanos().into()),
+            ("std_dev_ns", self.stats.std_dev.as_nanos().into()),
+            ("min_ns", self.stats.min.as_nanos().into()),
+            ("max_ns", self.stats.max.as_nanos().into()),
+        ])
+    }
+}
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let part = get_part(&mut args)?;
+    let input = args.option("--input")?;
+    let resolver = get_resolver(&mut args)?;
+    let slow = args.flag("--slow");
+    let format = args.option("--format")?;
+    let record = args.flag("--record");
+    let threshold = get_threshold(&mut args)?;
+    let settings = Settings::from_args(&mut args)?;
+    let day = args.parsed_positional::<u8>("day")?;
+    args.finish()?;
+
+    let registry = get_registry();
+
+    let puzzles: Vec<&dyn Puzzle> = match day {
+        Some(day) => vec![registry.get(day).ok_or(Error::UnknownDay(day))?],
+        None if input.is_none() => registry.iter().collect(),
+        None => return Err(Error::Usage(format!("usage:\n{USAGE}"))),
+    };
+
+    let is_json = match format.as_deref() {
+        None | Some("table") => false,
+        Some("json") => true,
+        Some(format) => return Err(Error::Usage(format!("unknown format: {format}"))),
+    };
+
+    // Parts recorded as slow in answers.toml are left out unless asked for.
+    let answers = Answers::find().ok();
+    let mut measurements = vec![];
+
+    for puzzle in puzzles {
+        let day = puzzle.get_day();
+        let slow_parts = answers
+            .as_ref()
+            .and_then(|answers| answers.get(day, resolver.get_profile()).or_else(|| answers.get(day, PUZZLE_INPUT)))
+            .map(|expectation| expectation.slow.clone())
+            .unwrap_or_default();
+
+        let parts: Vec<Part> = Part::ALL
+            .into_iter()
+            .filter(|candidate| part.is_none_or(|part| part == *candidate))
+            .filter(|part| {
+                let skip = !slow && slow_parts.contains(part);
+                if skip {
+                    eprintln!("day {day} part {part} is slow, skipped (pass --slow to include it)");
+                }
+                !skip
+            })
+            .collect();
+
+        let source = resolver.resolve(day, input.as_deref())?;
+        let text = read_source(&source)?;
+
+        measurements.extend(bench(puzzle, &text, &parts, &settings).map_err(|error| error.with_file(&source))?);
+    }
+
+    if is_json {
+        println!("{}", Json::Array(measurements.iter().map(Measurement::to_json).collect()));
+    } else {
+        print!("{}", create_table(&measurements));
+    }
+
+    if record {
+        let state_dir = resolver.get_input_dir();
+        let mut history = History::load(&state_dir)?;
+        let run = history.record(&measurements, get_unix_time().as_secs(), &get_git_revision())?;
+
+        eprintln!("recorded run {run}");
+
+        check_regressions(&history, run, threshold)?;
+    }
+
+    Ok(())
+}
+
+/// Times parsing and each of `parts` separately, after a few untimed warm-up runs.
+pub fn bench(puzzle: &dyn Puzzle, input: &str, parts: &[Part], settings: &Settings) -> std::result::Result<Vec<Measurement>, ParseError> {
+    let parsed = puzzle.parse(input)?;
+
+    let measurement = |stage: Stage, samples: Vec<Duration>| Measurement {
+        day: puzzle.get_day(),
+        title: puzzle.get_title(),
+        stage,
+        stats: Stats::new(&samples),
+    };
+
+    let mut measurements = vec![measurement(
+        Stage::Parse,
+        sample(settings, || {
+            let start = Instant::now();
+            let parsed = black_box(puzzle.parse(black_box(input)));
+            let duration = start.elapsed();
+            drop(parsed);
+            duration
+        }),
+    )];
+
+    for &part in parts {
+        let samples = sample(settings, || {
+            let start = Instant::now();
+            let answer = black_box(parsed.solve(black_box(part)));
+            let duration = start.elapsed();
+            drop(answer);
+            duration
+        });
+
+        measurements.push(measurement(Stage::Part(part), samples));
+    }
+
+    Ok(measurements)
+}
+
+fn sample(settings: &Settings, mut run: impl FnMut() -> Duration) -> Vec<Duration> {
+    let start = Instant::now();
+
+    for _ in 0..settings.warmup {
+        run();
+
+        if start.elapsed() >= settings.max_time {
+            break;
+        }
+    }
+
+    let start = Instant::now();
+    let mut samples = vec![];
+
+    while samples.len() < settings.iterations && (samples.is_empty() || start.elapsed() < settings.max_time) {
+        samples.push(run());
+    }
+
+    samples
+}
+
+fn create_table(measurements: &[Measurement]) -> Table {
+    let mut table = Table::new(&[
+        ("Day", Align::Right),
+        ("Title", Align::Left),
+        ("Stage", Align::Right),
+        ("Runs", Align::Right),
+        ("Mean", Align::Right),
+        ("Median", Align::Right),
+        ("Std dev", Align::Right),
+        ("Min", Align::Right),
+    ]);
+
+    for measurement in measurements {
+        table.push(vec![
+            measurement.day.to_string(),
+            measurement.title.to_string(),
+            measurement.stage.to_string(),
+            measurement.stats.iterations.to_string(),
+            format_duration(measurement.stats.mean),
+            format_duration(measurement.stats.median),
+            format_duration(measurement.stats.std_dev),
+            format_duration(measurement.stats.min),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].into_iter().map(Duration::from_micros).collect();
+
+        let stats = Stats::new(&samples);
+
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1291));
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.max, Duration::from_micros(4));
+
+        let single = Stats::new(&[Duration::from_millis(7)]);
+        assert_eq!(single.median, Duration::from_millis(7));
+        assert_eq!(single.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn measures_each_stage() {
+        let registry = get_registry();
+        let settings = Settings {
+            warmup: 1,
+            iterations: 5,
+            max_time: Duration::from_secs(1),
+        };
+
+        let measurements = bench(registry.get(6).unwrap(), "Time:      7  15   30\nDistance:  9  40  200", &Part::ALL, &settings).unwrap();
+
+        let stages: Vec<Stage> = measurements.iter().map(|measurement| measurement.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Part(Part::One), Stage::Part(Part::Two)]);
+        assert!(measurements.iter().all(|measurement| measurement.stats.iterations == 5));
+
+        let json = measurements[1].to_json().to_string();
+        assert!(json.starts_with("{\"day\":6,\"title\":\"Wait For It\",\"stage\":\"1\",\"iterations\":5,"));
+    }
+
+    #[test]
+    fn time_limit_keeps_one_sample() {
+        let settings = Settings {
+            warmup: 10,
+            iterations: 1000,
+            max_time: Duration::ZERO,
+        };
+
+        assert_eq!(sample(&settings, || Duration::from_nanos(1)).len(), 1);
+    }
+}
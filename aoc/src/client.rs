@@ -0,0 +1,226 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::Args;
+
+use crate::error::{Error, Result};
+use crate::http::{self, Request, Response};
+
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const REQUEST_INTERVAL_VARIABLE: &str = "AOC_REQUEST_INTERVAL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2023";
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/NotSilent/AOC-2023-Rust";
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+/// Keeps at least `interval` between requests, across separate runs of the binary.
+pub struct RateLimiter {
+    stamp: PathBuf,
+    interval: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(state_dir: &Path, interval: Duration) -> Self {
+        RateLimiter {
+            stamp: state_dir.join(LAST_REQUEST_FILE),
+            interval,
+        }
+    }
+
+    pub fn wait(&self) -> Result<()> {
+        let now = get_unix_time().as_millis();
+
+        if let Ok(text) = fs::read_to_string(&self.stamp) {
+            if let Ok(last) = text.trim().parse::<u128>() {
+                let elapsed = Duration::from_millis(now.saturating_sub(last) as u64);
+
+                // Stamps are whole milliseconds, so wait one more to never come in early.
+                if elapsed < self.interval {
+                    thread::sleep(self.interval - elapsed + Duration::from_millis(1));
+                }
+            }
+        }
+
+        if let Some(parent) = self.stamp.parent() {
+            fs::create_dir_all(parent).map_err(|error| Error::Io(parent.to_path_buf(), error))?;
+        }
+
+        fs::write(&self.stamp, get_unix_time().as_millis().to_string())
+            .map_err(|error| Error::Io(self.stamp.clone(), error))
+    }
+}
+
+pub fn get_unix_time() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+}
+
+/// Authenticated access to the puzzle website.
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    limiter: RateLimiter,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>, limiter: RateLimiter) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            limiter,
+        }
+    }
+
+    /// Takes `--base-url`, `--session` and `--interval` (seconds), falling back to the environment.
+    pub fn from_args(args: &mut Args, state_dir: &Path) -> Result<Self> {
+        let base_url = args
+            .option("--base-url")?
+            .or_else(|| env::var(BASE_URL_VARIABLE).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        let session = args
+            .option("--session")?
+            .or_else(|| env::var(SESSION_VARIABLE).ok())
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+
+        let interval = match args.parsed_option::<f64>("--interval")? {
+            Some(seconds) => Some(seconds),
+            None => match env::var(REQUEST_INTERVAL_VARIABLE) {
+                Ok(value) => Some(value.parse::<f64>().map_err(|_| {
+                    Error::Usage(format!("invalid {REQUEST_INTERVAL_VARIABLE}: {value}"))
+                })?),
+                Err(_) => None,
+            },
+        };
+
+        let interval = match interval {
+            Some(seconds) if seconds.is_finite() && seconds >= 0.0 => Duration::from_secs_f64(seconds),
+            Some(seconds) => return Err(Error::Usage(format!("invalid request interval: {seconds}"))),
+            None => DEFAULT_REQUEST_INTERVAL,
+        };
+
+        Ok(Client::new(&base_url, session, RateLimiter::new(state_dir, interval)))
+    }
+
+    pub fn get(&self, path: &str) -> Result<Response> {
+        let request = self.create_request("GET", path)?;
+
+        self.send(request)
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<Response> {
+        let body = fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", encode_form_value(name), encode_form_value(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let request = self
+            .create_request("POST", path)?
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body);
+
+        self.send(request)
+    }
+
+    fn create_request(&self, method: &str, path: &str) -> Result<Request> {
+        let session = self.session.as_deref().ok_or(Error::MissingSession)?;
+
+        Ok(Request::new(method, &format!("{}{path}", self.base_url))
+            .header("Cookie", &format!("session={session}"))
+            .header("User-Agent", USER_AGENT))
+    }
+
+    fn send(&self, request: Request) -> Result<Response> {
+        self.limiter.wait()?;
+
+        http::send(&request)
+    }
+}
+
+fn encode_form_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            b' ' => "+".to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::get_header;
+    use crate::testing::{create_temp_dir, MockServer};
+    use std::time::Instant;
+
+    #[test]
+    fn session_cookie_and_user_agent() {
+        let dir = create_temp_dir("client-cookie");
+        let server = MockServer::start(|_| Response::new(200, "ok".to_string()));
+        let client = Client::new(&format!("{}/2023/", server.get_url()), Some("abc".to_string()), RateLimiter::new(&dir, Duration::ZERO));
+
+        let response = client.get("/day/1/input").unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "ok");
+
+        let requests = server.get_requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].target, "/2023/day/1/input");
+        assert_eq!(get_header(&requests[0].headers, "cookie"), Some("session=abc"));
+        assert_eq!(get_header(&requests[0].headers, "user-agent"), Some(USER_AGENT));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn form_body() {
+        let dir = create_temp_dir("client-form");
+        let server = MockServer::start(|_| Response::new(200, String::new()));
+        let client = Client::new(&server.get_url(), Some("abc".to_string()), RateLimiter::new(&dir, Duration::ZERO));
+
+        client.post_form("/day/1/answer", &[("level", "1"), ("answer", "a b&c")]).unwrap();
+
+        let requests = server.get_requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(get_header(&requests[0].headers, "content-type"), Some("application/x-www-form-urlencoded"));
+        assert_eq!(requests[0].body, "level=1&answer=a+b%26c");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_session() {
+        let dir = create_temp_dir("client-session");
+        let client = Client::new("http://127.0.0.1:9", None, RateLimiter::new(&dir, Duration::ZERO));
+
+        assert!(matches!(client.get("/day/1/input"), Err(Error::MissingSession)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rate_limiter_spaces_requests() {
+        let dir = create_temp_dir("client-limiter");
+        let limiter = RateLimiter::new(&dir, Duration::from_millis(200));
+
+        let start = Instant::now();
+        limiter.wait().unwrap();
+        limiter.wait().unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{
+    get_encrypted_path, get_file_name, get_key_file, Args, InputError, Key, KEY_FILE_VARIABLE,
+};
+
+use crate::error::{Error, Result};
+use crate::run::get_resolver;
+
+pub const USAGE: &str = "aoc encrypt <day>... [--profile <name>] [--remove]
+aoc encrypt --all [--profile <name>] [--remove]
+aoc encrypt --generate-key
+aoc decrypt <day>... [--profile <name>]
+aoc decrypt --all [--profile <name>]";
+
+pub fn execute_encrypt(mut args: Args) -> Result<()> {
+    if args.flag("--generate-key") {
+        args.finish()?;
+
+        let key_file = generate_key_file()?;
+        println!("wrote a new key to {}, keep a copy somewhere safe", key_file.display());
+
+        return Ok(());
+    }
+
+    let remove = args.flag("--remove");
+    let input_dir = get_resolver(&mut args)?.get_profile_dir();
+    let days = get_days(args, |day| input_dir.join(get_file_name(day)).is_file())?;
+    let key = Key::from_env()?;
+
+    for day in days {
+        let path = encrypt(&key, &input_dir, day, remove)?;
+        println!("day {day}: encrypted to {}", path.display());
+    }
+
+    Ok(())
+}
+
+pub fn execute_decrypt(mut args: Args) -> Result<()> {
+    let input_dir = get_resolver(&mut args)?.get_profile_dir();
+    let days = get_days(args, |day| get_encrypted_path(&input_dir.join(get_file_name(day))).is_file())?;
+    let key = Key::from_env()?;
+
+    for day in days {
+        let path = decrypt(&key, &input_dir, day)?;
+        println!("day {day}: decrypted to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// The days given on the command line, or with `--all` every day for which `exists` holds.
+fn get_days(mut args: Args, exists: impl Fn(u8) -> bool) -> Result<Vec<u8>> {
+    let all = args.flag("--all");
+
+    let mut days = vec![];
+    while let Some(day) = args.parsed_positional::<u8>("day")? {
+        days.push(day);
+    }
+    args.finish()?;
+
+    if all {
+        days.extend((1..=25).filter(|&day| exists(day)));
+    }
+
+    if days.is_empty() {
+        return Err(Error::Usage(format!("usage:\n{USAGE}")));
+    }
+
+    Ok(days)
+}
+
+/// Writes `dayNN.txt.enc` next to a day's input, optionally removing the plain copy once
+/// the encrypted one is known to decrypt back to it.
+pub fn encrypt(key: &Key, input_dir: &Path, day: u8, remove: bool) -> Result<PathBuf> {
+    let path = input_dir.join(get_file_name(day));
+    let encrypted_path = get_encrypted_path(&path);
+
+    let text = fs::read(&path).map_err(|error| Error::Io(path.clone(), error))?;
+    let data = key.encrypt(&text);
+
+    if key.decrypt(&data).ok().as_ref() != Some(&text) {
+        return Err(Error::Usage(format!("{}: encryption did not round-trip, nothing written", path.display())));
+    }
+
+    fs::write(&encrypted_path, data).map_err(|error| Error::Io(encrypted_path.clone(), error))?;
+
+    if remove {
+        fs::remove_file(&path).map_err(|error| Error::Io(path.clone(), error))?;
+    }
+
+    Ok(encrypted_path)
+}
+
+/// Writes a day's plain input back from `dayNN.txt.enc`, never overwriting a different one.
+pub fn decrypt(key: &Key, input_dir: &Path, day: u8) -> Result<PathBuf> {
+    let path = input_dir.join(get_file_name(day));
+    let encrypted_path = get_encrypted_path(&path);
+
+    let data = fs::read(&encrypted_path).map_err(|error| Error::Io(encrypted_path.clone(), error))?;
+    let text = key
+        .decrypt(&data)
+        .map_err(|error| InputError::Encryption(encrypted_path.clone(), error))?;
+
+    match fs::read(&path) {
+        Ok(existing) if existing == text => return Ok(path),
+        Ok(_) => {
+            return Err(Error::Usage(format!("{} already exists and differs, not overwriting it", path.display())));
+        }
+        Err(_) => {}
+    }
+
+    fs::write(&path, text).map_err(|error| Error::Io(path.clone(), error))?;
+
+    Ok(path)
+}
+
+fn generate_key_file() -> Result<PathBuf> {
+    let key_file = get_key_file()
+        .ok_or_else(|| Error::Usage(format!("cannot tell where to store the key, set {KEY_FILE_VARIABLE}")))?;
+
+    if key_file.exists() {
+        return Err(Error::Usage(format!("{} already exists, not overwriting it", key_file.display())));
+    }
+
+    if let Some(parent) = key_file.parent() {
+        fs::create_dir_all(parent).map_err(|error| Error::Io(parent.to_path_buf(), error))?;
+    }
+
+    let key = Key::generate().map_err(|error| Error::Io(PathBuf::from("/dev/urandom"), error))?;
+    fs::write(&key_file, format!("{}\n", key.to_hex())).map_err(|error| Error::Io(key_file.clone(), error))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(&key_file, fs::Permissions::from_mode(0o600))
+            .map_err(|error| Error::Io(key_file.clone(), error))?;
+    }
+
+    Ok(key_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::create_temp_dir;
+
+    const INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+    fn create_key() -> Key {
+        Key::parse("2b7e151628aed2a6abf7158809cf4f3c2b7e151628aed2a6abf7158809cf4f3c", "test").unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = create_temp_dir("encrypt-round-trip");
+        fs::write(dir.join("day06.txt"), INPUT).unwrap();
+
+        let encrypted_path = encrypt(&create_key(), &dir, 6, true).unwrap();
+
+        assert_eq!(encrypted_path, dir.join("day06.txt.enc"));
+        assert!(!dir.join("day06.txt").exists());
+        assert!(!fs::read(&encrypted_path).unwrap().windows(5).any(|window| window == b"Time:"));
+
+        let path = decrypt(&create_key(), &dir, 6).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), INPUT);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn decrypt_keeps_different_input() {
+        let dir = create_temp_dir("encrypt-overwrite");
+        fs::write(dir.join("day06.txt"), INPUT).unwrap();
+        encrypt(&create_key(), &dir, 6, false).unwrap();
+
+        assert!(decrypt(&create_key(), &dir, 6).is_ok());
+
+        fs::write(dir.join("day06.txt"), "Time: 1\nDistance: 1\n").unwrap();
+        let error = decrypt(&create_key(), &dir, 6).err().unwrap();
+        assert!(error.to_string().contains("not overwriting"));
+
+        let wrong_key = Key::parse(&"00".repeat(32), "test").unwrap();
+        let error = decrypt(&wrong_key, &dir, 6).err().unwrap();
+        assert!(error.to_string().contains("key is wrong"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use aoc_common::{AnswersError, EncryptionError, InputError, ParseError};
+
+use crate::client::SESSION_VARIABLE;
+use crate::journal::Refusal;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Usage(String),
+    UnknownDay(u8),
+    Input(InputError),
+    Parse(ParseError),
+    Io(PathBuf, io::Error),
+    Http(String),
+    MissingSession,
+    Refused(Refusal),
+    Answers(AnswersError),
+    Verify(usize),
+    Regressions(usize),
+    Encryption(EncryptionError),
+    Failed(usize),
+    Crashes(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{message}"),
+            Error::UnknownDay(day) => write!(f, "day {day} is not registered"),
+            Error::Input(error) => write!(f, "{error}"),
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::Io(path, error) => write!(f, "{}: {error}", path.display()),
+            Error::Http(message) => write!(f, "{message}"),
+            Error::MissingSession => write!(f, "no session token, pass --session or set {SESSION_VARIABLE}"),
+            Error::Refused(refusal) => write!(f, "not submitting: {refusal}"),
+            Error::Answers(error) => write!(f, "{error}"),
+            Error::Verify(failures) => write!(f, "{failures} answer(s) did not verify"),
+            Error::Regressions(regressions) => write!(f, "{regressions} stage(s) regressed against the baseline"),
+            Error::Encryption(error) => write!(f, "{error}"),
+            Error::Failed(days) => write!(f, "{days} day(s) failed"),
+            Error::Crashes(crashes) => write!(f, "{crashes} crashing input(s) saved, they now fail as tests"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(value: String) -> Self {
+        Error::Usage(value)
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(value: InputError) -> Self {
+        Error::Input(value)
+    }
+}
+
+impl From<AnswersError> for Error {
+    fn from(value: AnswersError) -> Self {
+        Error::Answers(value)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        Error::Parse(value)
+    }
+}
+
+impl From<EncryptionError> for Error {
+    fn from(value: EncryptionError) -> Self {
+        Error::Encryption(value)
+    }
+}
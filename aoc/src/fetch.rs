@@ -0,0 +1,161 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{get_encrypted_path, get_file_name, Args};
+use aoc2023::get_registry;
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::run::get_resolver;
+
+pub const USAGE: &str = "aoc fetch <day>... [--profile <name>] [--base-url <url>] [--session <token>] [--interval <seconds>]
+aoc fetch --all [--profile <name>]";
+
+pub enum FetchOutcome {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let all = args.flag("--all");
+    let resolver = get_resolver(&mut args)?;
+    let client = Client::from_args(&mut args, &resolver.get_input_dir())?;
+    let input_dir = resolver.get_profile_dir();
+
+    let mut days = vec![];
+    while let Some(day) = args.parsed_positional::<u8>("day")? {
+        days.push(day);
+    }
+    args.finish()?;
+
+    if all {
+        days.extend(get_registry().iter().map(|puzzle| puzzle.get_day()));
+    }
+
+    if days.is_empty() {
+        return Err(Error::Usage(format!("usage:\n{USAGE}")));
+    }
+
+    for day in days {
+        match fetch(&client, &input_dir, day)? {
+            FetchOutcome::Downloaded(path) => println!("day {day}: downloaded to {}", path.display()),
+            FetchOutcome::Cached(path) => println!("day {day}: already cached at {}", path.display()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads a day's input into `input_dir`, unless it is already there.
+pub fn fetch(client: &Client, input_dir: &Path, day: u8) -> Result<FetchOutcome> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!("there is no day {day}")));
+    }
+
+    let path = input_dir.join(get_file_name(day));
+
+    if path.is_file() {
+        return Ok(FetchOutcome::Cached(path));
+    }
+
+    let encrypted_path = get_encrypted_path(&path);
+    if encrypted_path.is_file() {
+        return Ok(FetchOutcome::Cached(encrypted_path));
+    }
+
+    let response = client.get(&format!("/day/{day}/input"))?;
+
+    if !response.is_success() {
+        return Err(Error::Http(format!(
+            "fetching day {day} failed with status {}: {}",
+            response.status,
+            response.body.trim()
+        )));
+    }
+
+    fs::create_dir_all(input_dir).map_err(|error| Error::Io(input_dir.to_path_buf(), error))?;
+
+    let partial = path.with_extension("part");
+    fs::write(&partial, &response.body).map_err(|error| Error::Io(partial.clone(), error))?;
+    fs::rename(&partial, &path).map_err(|error| Error::Io(path.clone(), error))?;
+
+    Ok(FetchOutcome::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::RateLimiter;
+    use crate::http::{get_header, Response};
+    use crate::testing::{create_temp_dir, MockServer};
+    use std::time::Duration;
+
+    fn create_server() -> MockServer {
+        MockServer::start(|request| {
+            if get_header(&request.headers, "Cookie") != Some("session=secret") {
+                return Response::new(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string());
+            }
+
+            match request.target.as_str() {
+                "/2023/day/6/input" => Response::new(200, "Time:      7  15   30\nDistance:  9  40  200\n".to_string()),
+                _ => Response::new(404, "404 Not Found".to_string()),
+            }
+        })
+    }
+
+    fn create_client(server: &MockServer, dir: &Path, session: &str) -> Client {
+        Client::new(
+            &format!("{}/2023", server.get_url()),
+            Some(session.to_string()),
+            RateLimiter::new(dir, Duration::ZERO),
+        )
+    }
+
+    #[test]
+    fn downloads_then_uses_cache() {
+        let dir = create_temp_dir("fetch-cache");
+        let server = create_server();
+        let client = create_client(&server, &dir, "secret");
+
+        let first = fetch(&client, &dir.join("inputs"), 6).unwrap();
+        let second = fetch(&client, &dir.join("inputs"), 6).unwrap();
+
+        assert!(matches!(first, FetchOutcome::Downloaded(_)));
+        assert!(matches!(second, FetchOutcome::Cached(_)));
+        assert_eq!(server.get_requests().len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.join("inputs").join("day06.txt")).unwrap(),
+            "Time:      7  15   30\nDistance:  9  40  200\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_download_leaves_no_file() {
+        let dir = create_temp_dir("fetch-failure");
+        let server = create_server();
+
+        let error = fetch(&create_client(&server, &dir, "wrong"), &dir, 6).err().unwrap();
+        assert!(error.to_string().contains("status 400"));
+
+        let error = fetch(&create_client(&server, &dir, "secret"), &dir, 7).err().unwrap();
+        assert!(error.to_string().contains("status 404"));
+
+        assert!(!dir.join("day06.txt").exists());
+        assert!(!dir.join("day07.txt").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_day() {
+        let dir = create_temp_dir("fetch-day");
+        let server = create_server();
+
+        assert!(fetch(&create_client(&server, &dir, "secret"), &dir, 26).is_err());
+        assert!(server.get_requests().is_empty());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,159 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use aoc_common::{find_examples_dir, Args, Finding, FuzzTarget, Puzzle, FUZZ_DIRECTORY};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+
+pub const USAGE: &str = "aoc fuzz [<day>] [--target <parse|part1|part2>] [--runs <n>] [--seed <n>] [--max-len <bytes>]";
+
+const DEFAULT_RUNS: usize = 20_000;
+const DEFAULT_MAX_LEN: usize = 2048;
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let target = args.parsed_option::<FuzzTarget>("--target")?;
+    let runs = args.parsed_option::<usize>("--runs")?.unwrap_or(DEFAULT_RUNS);
+    let seed = args.parsed_option::<u64>("--seed")?;
+    let max_len = args.parsed_option::<usize>("--max-len")?.unwrap_or(DEFAULT_MAX_LEN);
+    let day = args.parsed_positional::<u8>("day")?;
+    args.finish()?;
+
+    // Without --seed every run explores differently; the seed is printed to reproduce it.
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    });
+
+    let registry = get_registry();
+
+    let puzzles: Vec<&dyn Puzzle> = match day {
+        Some(day) => vec![registry.get(day).ok_or(Error::UnknownDay(day))?],
+        None => registry.iter().collect(),
+    };
+
+    let targets: Vec<FuzzTarget> = match target {
+        Some(target) => vec![target],
+        None => FuzzTarget::ALL.to_vec(),
+    };
+
+    let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut crashes = 0;
+
+    println!("seed {seed}");
+
+    for puzzle in puzzles {
+        let day = puzzle.get_day();
+        let Some(examples_dir) = find_examples_dir(&current_dir, day) else {
+            return Err(Error::Usage(format!("cannot find day{day:02}/examples from {}", current_dir.display())));
+        };
+        let fuzz_dir = examples_dir.with_file_name(FUZZ_DIRECTORY);
+        let corpus = get_corpus(&[&examples_dir, &fuzz_dir])?;
+
+        for &target in &targets {
+            let start = Instant::now();
+            let findings = aoc_common::fuzz(puzzle, target, &corpus, runs, seed, max_len);
+
+            println!(
+                "day {day} {target}: {runs} runs from {} seed input(s) in {:.1}s, {} crash(es)",
+                corpus.len(),
+                start.elapsed().as_secs_f64(),
+                findings.len()
+            );
+
+            for finding in findings {
+                let path = save_finding(&fuzz_dir, &finding)?;
+                println!("  {}\n    {}", finding.message, path.display());
+                crashes += 1;
+            }
+        }
+    }
+
+    if crashes > 0 {
+        return Err(Error::Crashes(crashes));
+    }
+
+    Ok(())
+}
+
+/// Every `.txt` file in the given directories, which need not exist.
+fn get_corpus(dirs: &[&Path]) -> Result<Vec<String>> {
+    let mut corpus = vec![];
+
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            corpus.push(fs::read_to_string(&path).map_err(|error| Error::Io(path.clone(), error))?);
+        }
+    }
+
+    Ok(corpus)
+}
+
+fn save_finding(fuzz_dir: &Path, finding: &Finding) -> Result<PathBuf> {
+    fs::create_dir_all(fuzz_dir).map_err(|error| Error::Io(fuzz_dir.to_path_buf(), error))?;
+
+    let path = fuzz_dir.join(finding.get_file_name());
+    fs::write(&path, &finding.input).map_err(|error| Error::Io(path.clone(), error))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    use crate::testing::create_temp_dir;
+
+    #[test]
+    fn corpus_and_findings() {
+        let dir = create_temp_dir("fuzz");
+        let fuzz_dir = dir.join("fuzz");
+        fs::write(dir.join("example_0.txt"), "1 2").unwrap();
+        fs::write(dir.join("expected.toml"), "").unwrap();
+
+        assert_eq!(get_corpus(&[&dir, &fuzz_dir]).unwrap(), vec!["1 2".to_string()]);
+
+        let finding = Finding {
+            target: FuzzTarget::Part(Part::One),
+            input: "3 4".to_string(),
+            message: "attempt to add with overflow".to_string(),
+        };
+        let path = save_finding(&fuzz_dir, &finding).unwrap();
+
+        assert!(path.starts_with(&fuzz_dir));
+        assert_eq!(get_corpus(&[&dir, &fuzz_dir]).unwrap(), vec!["1 2".to_string(), "3 4".to_string()]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn days_survive_fuzzing() {
+        let registry = get_registry();
+        let current_dir = env::current_dir().unwrap();
+
+        for puzzle in registry.iter() {
+            let examples_dir = find_examples_dir(&current_dir, puzzle.get_day()).unwrap();
+            let corpus = get_corpus(&[&examples_dir]).unwrap();
+
+            for target in FuzzTarget::ALL {
+                let findings = aoc_common::fuzz(puzzle, target, &corpus, 300, 2023, 512);
+
+                assert_eq!(findings, vec![], "day {} {target}", puzzle.get_day());
+            }
+        }
+    }
+}
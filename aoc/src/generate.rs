@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use aoc_common::{is_profile_name, Args, Generated, Part, Rng};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+
+pub const USAGE: &str = "aoc generate <day> [--seed <n>] [--output <path>] [--answers <name>] [<size options>]";
+
+/// [`USAGE`] followed by the size options of every day that has a generator.
+pub fn get_usage() -> String {
+    get_registry()
+        .iter()
+        .filter_map(|puzzle| Some(format!("\n  day {}: {}", puzzle.get_day(), puzzle.get_generator()?.options)))
+        .fold(USAGE.to_string(), |usage, day| usage + &day)
+}
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let seed = args.parsed_option::<u64>("--seed")?.unwrap_or(2023);
+    let output = args.option("--output")?.map(PathBuf::from);
+    let name = args.option("--answers")?;
+    let day = args
+        .parsed_positional::<u8>("day")?
+        .ok_or_else(|| Error::Usage(format!("missing day\nusage: {}", get_usage())))?;
+
+    if let Some(name) = name.as_deref().filter(|name| !is_profile_name(name)) {
+        return Err(Error::Usage(format!("invalid answers table name: {name}")));
+    }
+
+    let generated = generate(day, &mut Rng::new(seed), &mut args)?;
+    args.finish()?;
+
+    match &output {
+        Some(path) => fs::write(path, &generated.input).map_err(|error| Error::Io(path.clone(), error))?,
+        None => io::stdout()
+            .lock()
+            .write_all(generated.input.as_bytes())
+            .map_err(|error| Error::Io(PathBuf::from("<stdout>"), error))?,
+    }
+
+    if let Some(name) = name {
+        let table = format_answers(day, &name, &generated.answers);
+
+        // Answers go wherever the input does not, so that both can be redirected.
+        match output {
+            Some(_) => print!("{table}"),
+            None => eprint!("{table}"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates an input for `day` with the day's own generator, reading its size options from `args`.
+pub fn generate(day: u8, rng: &mut Rng, args: &mut Args) -> Result<Generated> {
+    let generator = get_registry()
+        .get(day)
+        .ok_or(Error::UnknownDay(day))?
+        .get_generator()
+        .ok_or_else(|| Error::Usage(format!("day {day} has no generator")))?;
+
+    Ok((generator.generate)(rng, args)?)
+}
+
+/// The answers as an `answers.toml` table, ready to paste.
+pub fn format_answers(day: u8, name: &str, answers: &BTreeMap<Part, String>) -> String {
+    let mut table = format!("[day{day:02}.{name}]\n");
+
+    for part in Part::ALL {
+        match answers.get(&part) {
+            Some(answer) => {
+                let _ = writeln!(table, "{} = {answer}", aoc_common::get_part_key(part));
+            }
+            None => {
+                let _ = writeln!(table, "# {} does not fit in 64 bits", aoc_common::get_part_key(part));
+            }
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{get_answers, Answer};
+
+    fn generate_with(day: u8, seed: u64, options: &[&str]) -> Generated {
+        let mut args = Args::new(options.iter().map(|option| option.to_string()));
+        let generated = generate(day, &mut Rng::new(seed), &mut args).unwrap();
+        args.finish().unwrap();
+
+        generated
+    }
+
+    /// Solves the generated input, where a part the generator left out, because it does not fit
+    /// in 64 bits, must be one the solver cannot answer either.
+    fn assert_solver_agrees(day: u8, generated: &Generated, context: &str) {
+        let parsed = get_registry().get(day).unwrap().parse(&generated.input).unwrap();
+
+        for part in Part::ALL {
+            match (parsed.solve(part), generated.answers.get(&part)) {
+                (answer, Some(expected)) => assert_eq!(&answer.to_string(), expected, "day {day} part {part}, {context}"),
+                (answer, None) => assert!(matches!(answer, Answer::Unsolved(_)), "day {day} part {part} is {answer}, {context}"),
+            }
+        }
+    }
+
+    #[test]
+    fn solvers_agree_with_generated_answers() {
+        let options: [&[&str]; 6] = [
+            &["--lines", "300"],
+            &["--games", "200"],
+            &["--width", "60", "--height", "40"],
+            &["--cards", "150", "--match-rate", "40"],
+            &["--seed-ranges", "4", "--ranges", "12", "--max-value", "100000", "--max-seed-range", "500"],
+            &["--races", "3", "--max-time", "40"],
+        ];
+
+        for seed in [1, 2, 3] {
+            for (day, options) in (1..=6).zip(options) {
+                assert_solver_agrees(day, &generate_with(day, seed, options), &format!("seed {seed}"));
+            }
+        }
+    }
+
+    /// Inputs far larger than the puzzle's, some with answers past 64 bits, sized to take
+    /// seconds rather than minutes in a debug build.
+    #[test]
+    fn solvers_agree_at_scale() {
+        let inputs: [(u8, &[&str]); 10] = [
+            (1, &["--lines", "1000000"]),
+            (2, &["--games", "100000"]),
+            (3, &["--width", "5000", "--height", "5000"]),
+            (4, &["--cards", "100000"]),
+            (4, &["--cards", "100000", "--match-rate", "90"]),
+            (5, &["--seed-ranges", "1000", "--ranges", "3000", "--max-value", "4611686018427387904", "--max-seed-range", "1000000000000"]),
+            (6, &["--races", "1", "--max-time", "100000000000"]),
+            (6, &["--races", "1", "--max-time", "18446744073709551615"]),
+            (6, &["--races", "3", "--max-time", "1000000"]),
+            (6, &["--races", "4", "--max-time", "18446744073709551615"]),
+        ];
+
+        for (day, options) in inputs {
+            assert_solver_agrees(day, &generate_with(day, 1, options), &options.join(" "));
+        }
+    }
+
+    #[test]
+    fn seeds_reproduce_inputs() {
+        for day in 1..=6 {
+            assert_eq!(generate_with(day, 5, &[]).input, generate_with(day, 5, &[]).input);
+            assert_ne!(generate_with(day, 5, &[]).input, generate_with(day, 6, &[]).input);
+        }
+    }
+
+    #[test]
+    fn sizes_and_limits() {
+        assert_eq!(generate_with(1, 1, &["--lines", "7"]).input.lines().count(), 7);
+
+        let schematic = generate_with(3, 1, &["--width", "13", "--height", "5"]).input;
+        assert!(schematic.lines().all(|line| line.len() == 13));
+        assert_eq!(schematic.lines().count(), 5);
+
+        // A single race may take as long as a u64 allows; its answer is still known.
+        let race = generate_with(6, 1, &["--races", "1", "--max-time", "18446744073709551615"]);
+        assert_eq!(race.answers.len(), 2);
+
+        // Several of them no longer join into one for part two.
+        let races = generate_with(6, 1, &["--races", "3", "--max-time", "18446744073709551615"]);
+        assert!(!races.answers.contains_key(&Part::Two));
+
+        let mut args = Args::new(["--winning".to_string(), "50".to_string(), "--numbers".to_string(), "50".to_string()]);
+        assert!(generate(4, &mut Rng::new(1), &mut args).is_err());
+        assert!(matches!(generate(7, &mut Rng::new(1), &mut Args::new([])), Err(Error::UnknownDay(7))));
+        assert!(get_usage().ends_with("\n  day 6: --races <n> --max-time <n>"));
+    }
+
+    #[test]
+    fn answers_table() {
+        let answers = get_answers(Some(35), None);
+
+        assert_eq!(
+            format_answers(5, "large", &answers),
+            "[day05.large]\npart_one = 35\n# part_two does not fit in 64 bits\n"
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_common::{format_duration, Args, InputResolver};
+
+use crate::bench::{Measurement, Stage, Stats};
+use crate::error::{Error, Result};
+use crate::table::{Align, Table};
+
+pub const USAGE: &str = "aoc history [list]
+aoc history baseline [<run>]
+aoc history compare [<run>] [--threshold <percent>]";
+
+pub const HISTORY_FILE: &str = "bench-history.tsv";
+pub const BASELINE_FILE: &str = "bench-baseline";
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+const HEADER: &str = "# run\ttime\trevision\tday\tstage\titerations\tmean_ns\tmedian_ns\tstd_dev_ns\tmin_ns\tmax_ns";
+
+/// One measured stage of one recorded benchmark run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub run: u64,
+    pub time: u64,
+    pub revision: String,
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.stats;
+
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.run,
+            self.time,
+            self.revision,
+            self.day,
+            self.stage,
+            stats.iterations,
+            stats.mean.as_nanos(),
+            stats.median.as_nanos(),
+            stats.std_dev.as_nanos(),
+            stats.min.as_nanos(),
+            stats.max.as_nanos()
+        )
+    }
+}
+
+impl FromStr for Record {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid history record: {value}");
+
+        let fields: Vec<&str> = value.split('\t').collect();
+        if fields.len() != 11 {
+            return Err(invalid());
+        }
+
+        let nanos = |index: usize| fields[index].parse::<u64>().map(Duration::from_nanos).map_err(|_| invalid());
+
+        Ok(Record {
+            run: fields[0].parse().map_err(|_| invalid())?,
+            time: fields[1].parse().map_err(|_| invalid())?,
+            revision: fields[2].to_string(),
+            day: fields[3].parse().map_err(|_| invalid())?,
+            stage: fields[4].parse().map_err(|_| invalid())?,
+            stats: Stats {
+                iterations: fields[5].parse().map_err(|_| invalid())?,
+                mean: nanos(6)?,
+                median: nanos(7)?,
+                std_dev: nanos(8)?,
+                min: nanos(9)?,
+                max: nanos(10)?,
+            },
+        })
+    }
+}
+
+/// Every recorded benchmark run, appended to a tab separated file next to the inputs.
+pub struct History {
+    path: PathBuf,
+    baseline_path: PathBuf,
+    records: Vec<Record>,
+}
+
+impl History {
+    pub fn load(state_dir: &Path) -> Result<Self> {
+        let path = state_dir.join(HISTORY_FILE);
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(Error::Io(path, error)),
+        };
+
+        let records = text
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.parse::<Record>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|error| Error::Usage(format!("{}: {error}", path.display())))?;
+
+        Ok(History {
+            path,
+            baseline_path: state_dir.join(BASELINE_FILE),
+            records,
+        })
+    }
+
+    pub fn get_runs(&self) -> Vec<u64> {
+        let mut runs: Vec<u64> = self.records.iter().map(|record| record.run).collect();
+        runs.dedup();
+        runs
+    }
+
+    pub fn get_latest_run(&self) -> Option<u64> {
+        self.records.iter().map(|record| record.run).max()
+    }
+
+    pub fn get_records(&self, run: u64) -> Vec<&Record> {
+        self.records.iter().filter(|record| record.run == run).collect()
+    }
+
+    /// Appends the measurements as a new run and returns its number.
+    pub fn record(&mut self, measurements: &[Measurement], time: u64, revision: &str) -> Result<u64> {
+        let run = self.get_latest_run().map_or(1, |run| run + 1);
+        let io_error = |error| Error::Io(self.path.clone(), error);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let is_new = !self.path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error)?;
+
+        if is_new {
+            writeln!(file, "{HEADER}").map_err(io_error)?;
+        }
+
+        for measurement in measurements {
+            let record = Record {
+                run,
+                time,
+                revision: revision.to_string(),
+                day: measurement.day,
+                stage: measurement.stage,
+                stats: measurement.stats,
+            };
+
+            writeln!(file, "{record}").map_err(io_error)?;
+            self.records.push(record);
+        }
+
+        Ok(run)
+    }
+
+    pub fn get_baseline(&self) -> Result<Option<u64>> {
+        match fs::read_to_string(&self.baseline_path) {
+            Ok(text) => text
+                .trim()
+                .parse::<u64>()
+                .map(Some)
+                .map_err(|_| Error::Usage(format!("{}: invalid baseline run", self.baseline_path.display()))),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(Error::Io(self.baseline_path.clone(), error)),
+        }
+    }
+
+    pub fn set_baseline(&self, run: u64) -> Result<()> {
+        if self.get_records(run).is_empty() {
+            return Err(Error::Usage(format!("there is no recorded run {run}")));
+        }
+
+        fs::write(&self.baseline_path, format!("{run}\n")).map_err(|error| Error::Io(self.baseline_path.clone(), error))
+    }
+}
+
+/// Change of one stage between the baseline and another run.
+#[derive(Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Stats,
+    pub current: Stats,
+    /// Relative change of the mean, in percent.
+    pub change: f64,
+    pub is_significant: bool,
+    pub is_regression: bool,
+}
+
+/// Compares the stages both runs measured. A stage regresses when its mean grew by more than
+/// `threshold` percent and Welch's t-test says the difference is unlikely to be noise.
+pub fn compare(baseline: &[&Record], current: &[&Record], threshold: f64) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|record| {
+            let base = baseline
+                .iter()
+                .find(|base| base.day == record.day && base.stage == record.stage)?;
+
+            let base_mean = base.stats.mean.as_nanos() as f64;
+            let current_mean = record.stats.mean.as_nanos() as f64;
+            let change = if base_mean > 0.0 {
+                (current_mean - base_mean) / base_mean * 100.0
+            } else {
+                0.0
+            };
+            let is_significant = is_significant(&base.stats, &record.stats);
+
+            Some(Comparison {
+                day: record.day,
+                stage: record.stage,
+                baseline: base.stats,
+                current: record.stats,
+                change,
+                is_significant,
+                is_regression: is_significant && change > threshold,
+            })
+        })
+        .collect()
+}
+
+// Welch's t-test at the 95% level, two-sided.
+fn is_significant(a: &Stats, b: &Stats) -> bool {
+    let (n_a, n_b) = (a.iterations as f64, b.iterations as f64);
+    let (mean_a, mean_b) = (a.mean.as_nanos() as f64, b.mean.as_nanos() as f64);
+    let (var_a, var_b) = (
+        (a.std_dev.as_nanos() as f64).powi(2) / n_a,
+        (b.std_dev.as_nanos() as f64).powi(2) / n_b,
+    );
+
+    if mean_a == mean_b {
+        return false;
+    }
+
+    // Without any spread there is nothing to weigh the difference against.
+    if var_a + var_b == 0.0 {
+        return true;
+    }
+
+    if n_a < 2.0 || n_b < 2.0 {
+        return false;
+    }
+
+    let t = (mean_a - mean_b).abs() / (var_a + var_b).sqrt();
+    let degrees = (var_a + var_b).powi(2) / (var_a.powi(2) / (n_a - 1.0) + var_b.powi(2) / (n_b - 1.0));
+
+    t > get_critical_t(degrees)
+}
+
+fn get_critical_t(degrees: f64) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160, 2.145, 2.131,
+        2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+    ];
+
+    match degrees.floor() as usize {
+        0 => TABLE[0],
+        degrees if degrees <= TABLE.len() => TABLE[degrees - 1],
+        degrees if degrees <= 60 => 2.000,
+        degrees if degrees <= 120 => 1.980,
+        _ => 1.960,
+    }
+}
+
+/// Short hash of the checked out commit, with `-dirty` when there are local changes.
+pub fn get_git_revision() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(revision) if git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty()) => {
+            format!("{revision}-dirty")
+        }
+        Some(revision) => revision,
+        None => "unknown".to_string(),
+    }
+}
+
+pub fn get_threshold(args: &mut Args) -> Result<f64> {
+    let threshold = args.parsed_option::<f64>("--threshold")?.unwrap_or(DEFAULT_THRESHOLD);
+
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(Error::Usage(format!("invalid --threshold: {threshold}")));
+    }
+
+    Ok(threshold)
+}
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let state_dir = InputResolver::from_env().get_input_dir();
+    let threshold = get_threshold(&mut args)?;
+    let command = args.subcommand();
+    let run = args.parsed_positional::<u64>("run")?;
+    args.finish()?;
+
+    let history = History::load(&state_dir)?;
+
+    match command.as_deref() {
+        Some("list") | None => {
+            print!("{}", create_runs_table(&history, history.get_baseline()?));
+            Ok(())
+        }
+        Some("baseline") => {
+            let run = run
+                .or(history.get_latest_run())
+                .ok_or_else(|| Error::Usage("no recorded runs, use aoc bench --record".to_string()))?;
+
+            history.set_baseline(run)?;
+            println!("run {run} is the baseline");
+            Ok(())
+        }
+        Some("compare") => {
+            let run = run
+                .or(history.get_latest_run())
+                .ok_or_else(|| Error::Usage("no recorded runs, use aoc bench --record".to_string()))?;
+
+            check_regressions(&history, run, threshold)
+        }
+        Some(command) => Err(Error::Usage(format!("unknown history command: {command}\nusage:\n{USAGE}"))),
+    }
+}
+
+/// Prints how `run` compares to the baseline and fails when any stage regressed.
+pub fn check_regressions(history: &History, run: u64, threshold: f64) -> Result<()> {
+    let Some(baseline) = history.get_baseline()? else {
+        println!("no baseline, mark one with aoc history baseline [<run>]");
+        return Ok(());
+    };
+
+    let current = history.get_records(run);
+    if current.is_empty() {
+        return Err(Error::Usage(format!("there is no recorded run {run}")));
+    }
+
+    let comparisons = compare(&history.get_records(baseline), &current, threshold);
+
+    println!("run {run} against baseline run {baseline}, threshold {threshold}%");
+    print!("{}", create_comparison_table(&comparisons));
+
+    let regressions = comparisons.iter().filter(|comparison| comparison.is_regression).count();
+    if regressions > 0 {
+        return Err(Error::Regressions(regressions));
+    }
+
+    Ok(())
+}
+
+fn create_runs_table(history: &History, baseline: Option<u64>) -> Table {
+    let mut table = Table::new(&[
+        ("Run", Align::Right),
+        ("Time", Align::Right),
+        ("Revision", Align::Left),
+        ("Stages", Align::Right),
+        ("Total mean", Align::Right),
+        ("", Align::Left),
+    ]);
+
+    for run in history.get_runs() {
+        let records = history.get_records(run);
+
+        table.push(vec![
+            run.to_string(),
+            records[0].time.to_string(),
+            records[0].revision.clone(),
+            records.len().to_string(),
+            format_duration(records.iter().map(|record| record.stats.mean).sum()),
+            if baseline == Some(run) { "baseline".to_string() } else { String::new() },
+        ]);
+    }
+
+    table
+}
+
+fn create_comparison_table(comparisons: &[Comparison]) -> Table {
+    let mut table = Table::new(&[
+        ("Day", Align::Right),
+        ("Stage", Align::Right),
+        ("Baseline", Align::Right),
+        ("Current", Align::Right),
+        ("Change", Align::Right),
+        ("Status", Align::Left),
+    ]);
+
+    for comparison in comparisons {
+        let status = if comparison.is_regression {
+            "REGRESSION"
+        } else if !comparison.is_significant {
+            "~"
+        } else if comparison.change < 0.0 {
+            "faster"
+        } else {
+            "slower"
+        };
+
+        table.push(vec![
+            comparison.day.to_string(),
+            comparison.stage.to_string(),
+            format_duration(comparison.baseline.mean),
+            format_duration(comparison.current.mean),
+            format!("{:+.1}%", comparison.change),
+            status.to_string(),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::create_temp_dir;
+    use aoc_common::Part;
+
+    fn stats(mean: u64, std_dev: u64) -> Stats {
+        Stats {
+            iterations: 50,
+            mean: Duration::from_micros(mean),
+            median: Duration::from_micros(mean),
+            std_dev: Duration::from_micros(std_dev),
+            min: Duration::from_micros(mean - std_dev),
+            max: Duration::from_micros(mean + std_dev),
+        }
+    }
+
+    fn measurement(day: u8, stage: Stage, stats: Stats) -> Measurement {
+        Measurement {
+            day,
+            title: "Gear Ratios",
+            stage,
+            stats,
+        }
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let record = Record {
+            run: 3,
+            time: 1700000000,
+            revision: "4aa0d07-dirty".to_string(),
+            day: 3,
+            stage: Stage::Part(Part::Two),
+            stats: stats(37, 3),
+        };
+
+        assert_eq!(record.to_string(), "3\t1700000000\t4aa0d07-dirty\t3\t2\t50\t37000\t37000\t3000\t34000\t40000");
+        assert_eq!(record.to_string().parse::<Record>(), Ok(record));
+        assert!("3\t1\tabc\t3\tthree\t50\t1\t1\t1\t1\t1".parse::<Record>().is_err());
+    }
+
+    #[test]
+    fn significance() {
+        assert!(!is_significant(&stats(100, 20), &stats(102, 20)));
+        assert!(is_significant(&stats(100, 2), &stats(120, 2)));
+        assert!(is_significant(&stats(100, 0), &stats(101, 0)));
+        assert!(!is_significant(&stats(100, 5), &stats(100, 5)));
+    }
+
+    #[test]
+    fn regressions_against_baseline() {
+        let dir = create_temp_dir("history-regressions");
+        let mut history = History::load(&dir).unwrap();
+
+        let first = history
+            .record(
+                &[
+                    measurement(3, Stage::Part(Part::Two), stats(37, 2)),
+                    measurement(4, Stage::Part(Part::Two), stats(36, 2)),
+                ],
+                100,
+                "abc",
+            )
+            .unwrap();
+        let second = history
+            .record(
+                &[
+                    measurement(3, Stage::Part(Part::Two), stats(60, 2)),
+                    measurement(4, Stage::Part(Part::Two), stats(37, 2)),
+                ],
+                200,
+                "def",
+            )
+            .unwrap();
+
+        assert_eq!((first, second), (1, 2));
+        assert!(check_regressions(&history, second, 10.0).is_ok());
+
+        history.set_baseline(first).unwrap();
+        assert!(history.set_baseline(7).is_err());
+
+        let history = History::load(&dir).unwrap();
+        assert_eq!(history.get_runs(), vec![1, 2]);
+        assert_eq!(history.get_baseline().unwrap(), Some(1));
+
+        let comparisons = compare(&history.get_records(1), &history.get_records(2), 10.0);
+        assert!(comparisons[0].is_regression);
+        assert!(!comparisons[1].is_regression);
+
+        assert!(matches!(check_regressions(&history, second, 10.0), Err(Error::Regressions(1))));
+        assert!(check_regressions(&history, second, 100.0).is_ok());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,349 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq)]
+pub struct Url {
+    pub scheme: String,
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl TryFrom<&str> for Url {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        let invalid = || Error::Http(format!("invalid url: {value}"));
+
+        let (scheme, rest) = value.split_once("://").ok_or_else(invalid)?;
+        let default_port = match scheme {
+            "http" => 80,
+            "https" => 443,
+            _ => return Err(invalid()),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse::<u16>().map_err(|_| invalid())?),
+            None => (authority, default_port),
+        };
+
+        if host.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Url {
+            scheme: scheme.to_string(),
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn new(method: &str, target: &str) -> Self {
+        Request {
+            method: method.to_string(),
+            target: target.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(mut self, body: String) -> Self {
+        self.body = body;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, body: String) -> Self {
+        Response {
+            status,
+            body,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+pub fn get_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Sends `request` to the absolute url in its target.
+///
+/// Plain `http` is spoken directly over TCP, `https` is delegated to `curl`.
+pub fn send(request: &Request) -> Result<Response> {
+    let url = Url::try_from(request.target.as_str())?;
+
+    match url.scheme.as_str() {
+        "http" => send_tcp(&url, request)
+            .map_err(|error| Error::Http(format!("{}: {error}", request.target))),
+        _ => send_curl(request),
+    }
+}
+
+fn send_tcp(url: &Url, request: &Request) -> io::Result<Response> {
+    let mut stream = TcpStream::connect((url.host.as_str(), url.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", request.method, url.path, url.host);
+    for (name, value) in &request.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!("Content-Length: {}\r\n\r\n", request.body.len()));
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(request.body.as_bytes())?;
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("bad status line: {status_line:?}")))?;
+
+    let headers = read_headers(&mut reader)?;
+
+    let mut body = vec![];
+    reader.read_to_end(&mut body)?;
+
+    if get_header(&headers, "Transfer-Encoding").is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked")) {
+        body = decode_chunked(&body)?;
+    }
+
+    Ok(Response::new(status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+fn send_curl(request: &Request) -> Result<Response> {
+    // Headers go through stdin as a curl config so the session cookie never shows up in `ps`.
+    let mut config = String::new();
+    for (name, value) in &request.headers {
+        config.push_str(&format!("header = \"{}: {}\"\n", name, value.replace('"', "\\\"")));
+    }
+
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--max-time", "30", "--config", "-"])
+        .args(["--request", &request.method])
+        .args(["--write-out", "\n%{http_code}"]);
+
+    if !request.body.is_empty() {
+        command.args(["--data-binary", &request.body]);
+    }
+
+    let mut child = command
+        .arg(&request.target)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| Error::Http(format!("could not run curl: {error}")))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|error| Error::Http(format!("curl: {error}")))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|error| Error::Http(format!("curl: {error}")))?;
+
+    if !output.status.success() {
+        return Err(Error::Http(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim())));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+
+    Ok(Response::new(
+        status
+            .trim()
+            .parse()
+            .map_err(|_| Error::Http(format!("curl: bad status {status:?}")))?,
+        body.to_string(),
+    ))
+}
+
+pub fn read_headers<R: BufRead>(reader: &mut R) -> io::Result<Vec<(String, String)>> {
+    let mut headers = vec![];
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    Ok(headers)
+}
+
+/// Reads one request from a client, refusing bodies over `max_body` bytes.
+pub fn read_request(stream: &mut TcpStream, max_body: usize) -> io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method, target),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "bad request line")),
+    };
+
+    let headers = read_headers(&mut reader)?;
+
+    let length = get_header(&headers, "Content-Length")
+        .and_then(|length| length.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    if length > max_body {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("body of {length} bytes is over the limit of {max_body}"),
+        ));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method: method.to_string(),
+        target: target.to_string(),
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+pub fn write_response(stream: &mut TcpStream, response: &Response, content_type: &str) -> io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        get_reason(response.status),
+        response.body.len()
+    );
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+fn get_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Content",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+fn decode_chunked(body: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "bad chunked encoding");
+
+    let mut decoded = vec![];
+    let mut rest = body;
+
+    loop {
+        let line_end = rest.windows(2).position(|window| window == b"\r\n").ok_or_else(invalid)?;
+        let size_text = std::str::from_utf8(&rest[..line_end]).map_err(|_| invalid())?;
+        let size = usize::from_str_radix(size_text.split(';').next().unwrap_or("").trim(), 16).map_err(|_| invalid())?;
+        rest = &rest[line_end + 2..];
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        if rest.len() < size + 2 {
+            return Err(invalid());
+        }
+
+        decoded.extend_from_slice(&rest[..size]);
+        rest = &rest[size + 2..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_url() {
+        assert_eq!(
+            Url::try_from("http://127.0.0.1:8080/2023/day/1/input").unwrap(),
+            Url {
+                scheme: "http".to_string(),
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                path: "/2023/day/1/input".to_string(),
+            }
+        );
+
+        let url = Url::try_from("https://adventofcode.com").unwrap();
+        assert_eq!(url.port, 443);
+        assert_eq!(url.path, "/");
+
+        assert!(Url::try_from("ftp://example.com").is_err());
+        assert!(Url::try_from("http://:80/").is_err());
+    }
+
+    #[test]
+    fn chunked_body() {
+        let body = b"4\r\nWiki\r\n5;ext\r\npedia\r\n0\r\n\r\n";
+
+        assert_eq!(decode_chunked(body).unwrap(), b"Wikipedia");
+        assert!(decode_chunked(b"9\r\nshort\r\n").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aoc_common::Part;
+
+use crate::error::{Error, Result};
+
+pub const JOURNAL_FILE: &str = "submissions.tsv";
+
+const HEADER: &str = "# time\tday\tpart\tverdict\tcooldown_until\tanswer";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    Wait,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait => "wait",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown => "unknown",
+        };
+
+        write!(f, "{text}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "wait" => Ok(Verdict::Wait),
+            "already_solved" => Ok(Verdict::AlreadySolved),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict: {value}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub time: u64,
+    pub day: u8,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub cooldown_until: u64,
+    pub answer: String,
+}
+
+impl Entry {
+    fn is_for(&self, day: u8, part: Part) -> bool {
+        self.day == day && self.part == part
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.time, self.day, self.part, self.verdict, self.cooldown_until, self.answer
+        )
+    }
+}
+
+impl FromStr for Entry {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid journal entry: {value}");
+
+        let fields: Vec<&str> = value.splitn(6, '\t').collect();
+        if fields.len() != 6 {
+            return Err(invalid());
+        }
+
+        Ok(Entry {
+            time: fields[0].parse().map_err(|_| invalid())?,
+            day: fields[1].parse().map_err(|_| invalid())?,
+            part: fields[2]
+                .parse::<u8>()
+                .ok()
+                .and_then(|part| Part::try_from(part).ok())
+                .ok_or_else(invalid)?,
+            verdict: fields[3].parse()?,
+            cooldown_until: fields[4].parse().map_err(|_| invalid())?,
+            answer: fields[5].to_string(),
+        })
+    }
+}
+
+/// Why an answer is not worth sending.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    AlreadyRejected(Verdict),
+    AtOrAboveTooHigh(i128),
+    AtOrBelowTooLow(i128),
+    Cooldown(u64),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with answer {answer}"),
+            Refusal::AlreadyRejected(verdict) => write!(f, "this answer was already rejected ({verdict})"),
+            Refusal::AtOrAboveTooHigh(bound) => write!(f, "{bound} was already too high"),
+            Refusal::AtOrBelowTooLow(bound) => write!(f, "{bound} was already too low"),
+            Refusal::Cooldown(seconds) => write!(f, "wait {seconds}s before submitting again"),
+        }
+    }
+}
+
+/// Every verdict received so far, appended to a tab separated file.
+pub struct Journal {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(Error::Io(path.to_path_buf(), error)),
+        };
+
+        let entries = text
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.parse::<Entry>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|error| Error::Usage(format!("{}: {error}", path.display())))?;
+
+        Ok(Journal {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn get_entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn append(&mut self, entry: Entry) -> Result<()> {
+        let io_error = |error| Error::Io(self.path.clone(), error);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let is_new = !self.path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error)?;
+
+        if is_new {
+            writeln!(file, "{HEADER}").map_err(io_error)?;
+        }
+        writeln!(file, "{entry}").map_err(io_error)?;
+
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: u64) -> std::result::Result<(), Refusal> {
+        let entries = || self.entries.iter().filter(|entry| entry.is_for(day, part));
+
+        if let Some(entry) = entries().find(|entry| entry.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(entry.answer.clone()));
+        }
+
+        if let Some(entry) = entries().find(|entry| entry.verdict.is_rejection() && entry.answer == answer) {
+            return Err(Refusal::AlreadyRejected(entry.verdict));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let numbers = |verdict: Verdict| {
+                entries()
+                    .filter(move |entry| entry.verdict == verdict)
+                    .filter_map(|entry| entry.answer.parse::<i128>().ok())
+            };
+
+            if let Some(bound) = numbers(Verdict::TooHigh).min() {
+                if value >= bound {
+                    return Err(Refusal::AtOrAboveTooHigh(bound));
+                }
+            }
+
+            if let Some(bound) = numbers(Verdict::TooLow).max() {
+                if value <= bound {
+                    return Err(Refusal::AtOrBelowTooLow(bound));
+                }
+            }
+        }
+
+        // The cooldown applies to the whole account, not only to this puzzle.
+        let cooldown_until = self.entries.iter().map(|entry| entry.cooldown_until).max().unwrap_or(0);
+        if now < cooldown_until {
+            return Err(Refusal::Cooldown(cooldown_until - now));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::create_temp_dir;
+
+    fn entry(part: Part, verdict: Verdict, answer: &str) -> Entry {
+        Entry {
+            time: 100,
+            day: 5,
+            part,
+            verdict,
+            cooldown_until: 160,
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        let entry = entry(Part::Two, Verdict::TooLow, "7873083");
+
+        assert_eq!(entry.to_string(), "100\t5\t2\ttoo_low\t160\t7873083");
+        assert_eq!(entry.to_string().parse::<Entry>(), Ok(entry));
+        assert!("100\t5\t3\tcorrect\t0\t1".parse::<Entry>().is_err());
+    }
+
+    #[test]
+    fn persisted_between_loads() {
+        let dir = create_temp_dir("journal-persist");
+        let path = dir.join(JOURNAL_FILE);
+
+        let mut journal = Journal::load(&path).unwrap();
+        journal.append(entry(Part::One, Verdict::Wrong, "12")).unwrap();
+        journal.append(entry(Part::One, Verdict::Correct, "35")).unwrap();
+
+        let journal = Journal::load(&path).unwrap();
+        assert_eq!(journal.get_entries().len(), 2);
+        assert_eq!(journal.get_entries()[1].verdict, Verdict::Correct);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn refusals() {
+        let journal = Journal {
+            path: PathBuf::new(),
+            entries: vec![
+                entry(Part::One, Verdict::Correct, "35"),
+                entry(Part::Two, Verdict::TooHigh, "100"),
+                entry(Part::Two, Verdict::TooLow, "40"),
+                entry(Part::Two, Verdict::Wrong, "abc"),
+            ],
+        };
+
+        assert_eq!(journal.check(5, Part::One, "36", 200), Err(Refusal::AlreadySolved("35".to_string())));
+        assert_eq!(journal.check(5, Part::Two, "abc", 200), Err(Refusal::AlreadyRejected(Verdict::Wrong)));
+        assert_eq!(journal.check(5, Part::Two, "100", 200), Err(Refusal::AlreadyRejected(Verdict::TooHigh)));
+        assert_eq!(journal.check(5, Part::Two, "150", 200), Err(Refusal::AtOrAboveTooHigh(100)));
+        assert_eq!(journal.check(5, Part::Two, "12", 200), Err(Refusal::AtOrBelowTooLow(40)));
+        assert_eq!(journal.check(5, Part::Two, "46", 150), Err(Refusal::Cooldown(10)));
+        assert_eq!(journal.check(5, Part::Two, "46", 200), Ok(()));
+        assert_eq!(journal.check(6, Part::One, "150", 200), Ok(()));
+    }
+}
@@ -0,0 +1,67 @@
+mod all;
+mod bench;
+mod client;
+mod encrypt;
+mod error;
+mod fetch;
+mod fuzz;
+mod generate;
+mod history;
+mod http;
+mod journal;
+mod new;
+mod repl;
+mod run;
+mod serve;
+mod submit;
+mod table;
+mod verify;
+#[cfg(test)]
+mod testing;
+
+use std::process::ExitCode;
+
+use aoc_common::Args;
+
+use crate::error::{Error, Result};
+
+fn main() -> ExitCode {
+    match execute(Args::from_env()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn execute(mut args: Args) -> Result<()> {
+    aoc_common::init_tracing(&mut args)?;
+
+    match args.subcommand().as_deref() {
+        Some("run") => run::execute(args),
+        Some("all") => all::execute(args),
+        Some("fetch") => fetch::execute(args),
+        Some("fuzz") => fuzz::execute(args),
+        Some("generate") => generate::execute(args),
+        Some("submit") => submit::execute(args),
+        Some("verify") => verify::execute(args),
+        Some("new") => new::execute(args),
+        Some("bench") => bench::execute(args),
+        Some("history") => history::execute(args),
+        Some("serve") => serve::execute(args),
+        Some("repl") => repl::execute(args),
+        Some("encrypt") => encrypt::execute_encrypt(args),
+        Some("decrypt") => encrypt::execute_decrypt(args),
+        Some("help") | None => {
+            println!("usage:\n{}", get_usage());
+            Ok(())
+        }
+        Some(command) => Err(Error::Usage(format!("unknown command: {command}\nusage:\n{}", get_usage()))),
+    }
+}
+
+fn get_usage() -> String {
+    [run::USAGE, all::USAGE, fetch::USAGE, submit::USAGE, verify::USAGE, new::USAGE, bench::USAGE, history::USAGE, encrypt::USAGE, fuzz::USAGE, &generate::get_usage(), serve::USAGE, repl::USAGE].join("\n")
+        + "\n\nany command takes -v or -vv, or $AOC_LOG=info|debug, to trace the solvers"
+}
@@ -0,0 +1,362 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{Args, EXAMPLES_DIRECTORY, EXAMPLES_FILE};
+
+use crate::error::{Error, Result};
+
+pub const USAGE: &str = "aoc new <day> [--title <title>]";
+
+/// The crate whose `dayNN` dependencies make up the registry.
+const REGISTRY_CRATE: &str = "aoc2023";
+
+const MANIFEST_TEMPLATE: &str = "[package]
+name = \"{name}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = { path = \"../aoc-common\" }
+
+[build-dependencies]
+aoc-common = { path = \"../aoc-common\" }
+";
+
+const BUILD_TEMPLATE: &str = "fn main() {
+    aoc_common::generate_tests();
+}
+";
+
+const MAIN_TEMPLATE: &str = "use std::process::ExitCode;
+
+use {name}::{type};
+
+#[global_allocator]
+static ALLOCATOR: aoc_common::CountingAllocator = aoc_common::CountingAllocator;
+
+fn main() -> ExitCode {
+    aoc_common::day_main(&{type})
+}
+";
+
+const LIB_TEMPLATE: &str = "//! Day {day}: {title}
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::{Answer, ParseError, Solution};
+
+/// The puzzle input, one entry per line.
+pub struct Document {
+    lines: Vec<String>,
+}
+
+impl FromStr for Document {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::empty_input(value, \"expected the puzzle input\"));
+        }
+
+        let lines = value
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+
+        Ok(Document {
+            lines,
+        })
+    }
+}
+
+impl Document {
+    /// The lines of the input, in order.
+    pub fn get_lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+/// The [`Solution`] for day {day}.
+pub struct {type};
+
+impl Solution for {type} {
+    const DAY: u8 = {day};
+    const TITLE: &'static str = \"{title}\";
+
+    type Input = Document;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part_one(_input: &Self::Input) -> Answer {
+        Answer::Unsolved(\"not solved yet\".to_string())
+    }
+
+    fn part_two(_input: &Self::Input) -> Answer {
+        Answer::Unsolved(\"not solved yet\".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod examples {
+        include!(concat!(env!(\"OUT_DIR\"), \"/example_tests.rs\"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!(\"OUT_DIR\"), \"/fuzz_tests.rs\"));
+    }
+
+    include!(concat!(env!(\"OUT_DIR\"), \"/answer_tests.rs\"));
+}
+";
+
+const EXPECTED_TEMPLATE: &str = "# Expected answers for example_0.txt, for example:
+# part_one = 142
+[{name}.example_0]
+";
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let title = args.option("--title")?;
+    let day = args.parsed_positional::<u8>("day")?;
+    args.finish()?;
+
+    let Some(day) = day else {
+        return Err(Error::Usage(format!("usage:\n{USAGE}")));
+    };
+
+    let current_dir = env::current_dir().map_err(|error| Error::Io(PathBuf::from("."), error))?;
+    let root = find_workspace_root(&current_dir)
+        .ok_or_else(|| Error::Usage(format!("{} is not inside the workspace", current_dir.display())))?;
+
+    let title = title.unwrap_or_else(|| format!("Day {day}"));
+    let crate_dir = scaffold(&root, day, &title)?;
+
+    println!("created {} and registered it in {REGISTRY_CRATE}", crate_dir.display());
+    println!("once solved, add its answers to answers.toml");
+
+    Ok(())
+}
+
+fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|directory| {
+            fs::read_to_string(directory.join("Cargo.toml"))
+                .map(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]"))
+                .unwrap_or(false)
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Creates `dayNN` under the workspace `root`, lists it in the workspace members and adds it
+/// to the dependencies of the registry crate, which registers it.
+pub fn scaffold(root: &Path, day: u8, title: &str) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!("there is no day {day}")));
+    }
+
+    if title.contains(['"', '\\', '\n']) {
+        return Err(Error::Usage(format!("the title cannot contain quotes, backslashes or newlines: {title}")));
+    }
+
+    let name = format!("day{day:02}");
+    let crate_dir = root.join(&name);
+
+    if crate_dir.exists() {
+        return Err(Error::Usage(format!("{} already exists, not overwriting it", crate_dir.display())));
+    }
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path).map_err(|error| Error::Io(manifest_path.clone(), error))?;
+    let manifest = add_member(&manifest, &name)
+        .ok_or_else(|| Error::Usage(format!("{}: no workspace members list", manifest_path.display())))?;
+
+    let registry_path = root.join(REGISTRY_CRATE).join("Cargo.toml");
+    let registry = fs::read_to_string(&registry_path).map_err(|error| Error::Io(registry_path.clone(), error))?;
+    let registry = add_dependency(&registry, &name)
+        .ok_or_else(|| Error::Usage(format!("{}: no [dependencies] table", registry_path.display())))?;
+
+    let fill = |template: &str| {
+        template
+            .replace("{name}", &name)
+            .replace("{type}", &format!("Day{day:02}"))
+            .replace("{day}", &day.to_string())
+            .replace("{title}", title)
+    };
+
+    let examples_dir = crate_dir.join(EXAMPLES_DIRECTORY);
+    let files = [
+        (crate_dir.join("Cargo.toml"), fill(MANIFEST_TEMPLATE)),
+        (crate_dir.join("build.rs"), fill(BUILD_TEMPLATE)),
+        (crate_dir.join("src").join("main.rs"), fill(MAIN_TEMPLATE)),
+        (crate_dir.join("src").join("lib.rs"), fill(LIB_TEMPLATE)),
+        (examples_dir.join("example_0.txt"), String::new()),
+        (examples_dir.join(EXAMPLES_FILE), fill(EXPECTED_TEMPLATE)),
+    ];
+
+    for (path, content) in files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| Error::Io(parent.to_path_buf(), error))?;
+        }
+
+        fs::write(&path, content).map_err(|error| Error::Io(path.clone(), error))?;
+    }
+
+    fs::write(&manifest_path, manifest).map_err(|error| Error::Io(manifest_path, error))?;
+    fs::write(&registry_path, registry).map_err(|error| Error::Io(registry_path, error))?;
+
+    Ok(crate_dir)
+}
+
+/// Adds `member` to the `members = [...]` list of a workspace manifest, keeping it sorted.
+fn add_member(manifest: &str, member: &str) -> Option<String> {
+    let start = manifest.find("members = [")? + "members = [".len();
+    let end = start + manifest[start..].find(']')?;
+
+    let mut members: Vec<String> = manifest[start..end]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"').to_string())
+        .filter(|member| !member.is_empty())
+        .collect();
+
+    if !members.iter().any(|existing| existing == member) {
+        members.push(member.to_string());
+        members.sort();
+    }
+
+    let list = members
+        .iter()
+        .map(|member| format!("    \"{member}\""))
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    Some(format!("{}\n{list}\n{}", &manifest[..start], &manifest[end..]))
+}
+
+/// Adds `dependency` as a sibling path dependency to the `[dependencies]` of a manifest,
+/// keeping them sorted.
+fn add_dependency(manifest: &str, dependency: &str) -> Option<String> {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let start = lines.iter().position(|line| line.trim() == "[dependencies]")? + 1;
+    let end = start + lines[start..].iter().position(|line| line.trim_start().starts_with('[')).unwrap_or(lines.len() - start);
+
+    let mut dependencies: Vec<String> = lines[start..end]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect();
+
+    if !dependencies.iter().any(|line| line.split('=').next().map(str::trim) == Some(dependency)) {
+        dependencies.push(format!("{dependency} = {{ path = \"../{dependency}\" }}"));
+        dependencies.sort();
+    }
+
+    let mut section = dependencies.join("\n");
+    if end < lines.len() {
+        section.push('\n');
+    }
+
+    let mut updated: Vec<String> = lines[..start].iter().map(|line| line.to_string()).collect();
+    updated.push(section);
+    updated.extend(lines[end..].iter().map(|line| line.to_string()));
+
+    Some(updated.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::create_temp_dir;
+
+    const MANIFEST: &str = "[workspace]
+resolver = \"2\"
+
+members = [
+    \"aoc\",
+    \"day01\",
+    \"day03\"
+]";
+
+    const REGISTRY: &str = "[package]
+name = \"aoc2023\"
+
+[dependencies]
+aoc-common = { path = \"../aoc-common\" }
+day01 = { path = \"../day01\" }
+day03 = { path = \"../day03\" }
+
+[build-dependencies]
+aoc-common = { path = \"../aoc-common\" }
+";
+
+    #[test]
+    fn members() {
+        assert_eq!(
+            add_member(MANIFEST, "day02").unwrap(),
+            "[workspace]\nresolver = \"2\"\n\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day02\",\n    \"day03\"\n]"
+        );
+        assert_eq!(add_member(MANIFEST, "day01").unwrap(), MANIFEST);
+        assert!(add_member("[package]\nname = \"aoc\"\n", "day02").is_none());
+    }
+
+    #[test]
+    fn dependencies() {
+        assert_eq!(
+            add_dependency(REGISTRY, "day02").unwrap(),
+            REGISTRY.replace("day01 = { path = \"../day01\" }\n", "day01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\n")
+        );
+        assert_eq!(add_dependency(REGISTRY, "day01").unwrap(), REGISTRY);
+        assert_eq!(
+            add_dependency("[dependencies]\naoc-common = \"1\"", "day07").unwrap(),
+            "[dependencies]\naoc-common = \"1\"\nday07 = { path = \"../day07\" }\n"
+        );
+        assert!(add_dependency("[package]\nname = \"aoc\"\n", "day02").is_none());
+    }
+
+    #[test]
+    fn creates_day_once() {
+        let root = create_temp_dir("new-day");
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        fs::create_dir(root.join(REGISTRY_CRATE)).unwrap();
+        fs::write(root.join(REGISTRY_CRATE).join("Cargo.toml"), REGISTRY).unwrap();
+
+        let crate_dir = scaffold(&root, 7, "Camel Cards").unwrap();
+
+        let lib = fs::read_to_string(crate_dir.join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(lib.contains("const TITLE: &'static str = \"Camel Cards\";"));
+        assert!(!lib.contains("todo!"));
+        assert!(fs::read_to_string(crate_dir.join("src").join("main.rs")).unwrap().contains("use day07::Day07;"));
+        assert!(crate_dir.join("build.rs").is_file());
+        assert!(crate_dir.join("examples").join("example_0.txt").is_file());
+        assert!(fs::read_to_string(crate_dir.join("examples").join(EXAMPLES_FILE)).unwrap().contains("[day07.example_0]"));
+        assert!(fs::read_to_string(root.join("Cargo.toml")).unwrap().contains("    \"day03\",\n    \"day07\"\n]"));
+        let registry = fs::read_to_string(root.join(REGISTRY_CRATE).join("Cargo.toml")).unwrap();
+        assert_eq!(aoc_common::get_day_dependencies(&registry), vec![1, 3, 7]);
+
+        fs::write(crate_dir.join("src").join("lib.rs"), "solved").unwrap();
+
+        let error = scaffold(&root, 7, "Camel Cards").err().unwrap();
+        assert!(error.to_string().contains("already exists"));
+        assert_eq!(fs::read_to_string(crate_dir.join("src").join("lib.rs")).unwrap(), "solved");
+
+        assert!(scaffold(&root, 26, "Nope").is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn finds_workspace_root() {
+        let root = find_workspace_root(&env::current_dir().unwrap()).unwrap();
+
+        assert!(root.join("aoc").join("Cargo.toml").is_file());
+    }
+}
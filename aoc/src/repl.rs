@@ -0,0 +1,313 @@
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use aoc_common::{format_duration, get_records, read_source, Args, InputSource, Parsed, Part, Puzzle};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+use crate::run::get_resolver;
+
+pub const USAGE: &str = "aoc repl <day> [--input <path>] [--profile <name>]";
+
+const HELP: &str = "commands:
+  inspect          summarise the parsed model
+  query <...>      ask the model about one item, see below
+  run [1|2]        solve one part, or both, with timings
+  reload           read and parse the input again
+  load <path>      switch to another input
+  history          list the commands so far
+  !<n>, !!         repeat command <n>, or the last one
+  help             show this
+  quit             leave, as does end of input";
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let input = args.option("--input")?;
+    let resolver = get_resolver(&mut args)?;
+    let day = args
+        .parsed_positional::<u8>("day")?
+        .ok_or_else(|| Error::Usage(format!("missing day\nusage: {USAGE}")))?;
+    args.finish()?;
+
+    let puzzle = get_registry().get(day).ok_or(Error::UnknownDay(day))?;
+    let source = resolver.resolve(day, input.as_deref())?;
+
+    if source == InputSource::Stdin {
+        return Err(Error::Usage("the REPL reads its commands from stdin, pass the input as a file".to_string()));
+    }
+
+    let mut session = Session::new(puzzle, source);
+    let mut stdout = io::stdout();
+
+    writeln!(stdout, "day {day}: {}\n{}", puzzle.get_title(), session.load())
+        .and_then(|()| session.run(io::stdin().lock(), &mut stdout))
+        .map_err(|error| Error::Io(PathBuf::from("<stdout>"), error))
+}
+
+struct Session {
+    puzzle: &'static dyn Puzzle,
+    source: InputSource,
+    text: String,
+    parsed: Option<Box<dyn Parsed>>,
+    history: Vec<String>,
+}
+
+enum Step {
+    Print(String),
+    Quit,
+}
+
+impl Session {
+    fn new(puzzle: &'static dyn Puzzle, source: InputSource) -> Self {
+        Session {
+            puzzle,
+            source,
+            text: String::new(),
+            parsed: None,
+            history: vec![],
+        }
+    }
+
+    /// Reads commands until `quit` or the end of `input`, printing a prompt before each.
+    fn run<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> io::Result<()> {
+        let mut lines = input.lines();
+
+        loop {
+            write!(output, "day{:02}> ", self.puzzle.get_day())?;
+            output.flush()?;
+
+            let Some(line) = lines.next().transpose()? else {
+                writeln!(output)?;
+                return Ok(());
+            };
+
+            let line = match self.expand(line.trim()) {
+                // Like a shell, show what `!<n>` stood for.
+                Ok(expanded) if expanded != line.trim() => {
+                    writeln!(output, "{expanded}")?;
+                    expanded
+                }
+                Ok(line) => line,
+                Err(message) => {
+                    writeln!(output, "error: {message}")?;
+                    continue;
+                }
+            };
+
+            if line.is_empty() {
+                continue;
+            }
+
+            self.history.push(line.clone());
+
+            match self.handle(&line) {
+                Step::Print(text) => writeln!(output, "{text}")?,
+                Step::Quit => return Ok(()),
+            }
+        }
+    }
+
+    /// Replaces `!!` and `!<n>` with the command from the history.
+    fn expand(&self, line: &str) -> std::result::Result<String, String> {
+        let Some(reference) = line.strip_prefix('!') else {
+            return Ok(line.to_string());
+        };
+
+        let index = match reference {
+            "!" => self.history.len().checked_sub(1),
+            number => number.parse::<usize>().ok().and_then(|number| number.checked_sub(1)),
+        };
+
+        let command = index
+            .and_then(|index| self.history.get(index))
+            .ok_or_else(|| format!("no command {line} in the history"))?;
+
+        Ok(command.clone())
+    }
+
+    fn handle(&mut self, line: &str) -> Step {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let text = match words[..] {
+            ["quit"] | ["exit"] => return Step::Quit,
+            ["help"] => self.get_help(),
+            ["history"] => self.get_history(),
+            ["reload"] => self.load(),
+            ["load", path] => {
+                self.source = InputSource::Path(PathBuf::from(path));
+                self.load()
+            }
+            ["run"] => self.solve(&Part::ALL),
+            ["run", part] => match part.parse::<u8>().ok().and_then(|part| Part::try_from(part).ok()) {
+                Some(part) => self.solve(&[part]),
+                None => format!("error: there is no part {part}"),
+            },
+            ["inspect"] => match self.parsed.as_ref().and_then(|parsed| parsed.inspect()) {
+                Some(summary) => summary,
+                None => self.get_missing_model(),
+            },
+            ["query", ref arguments @ ..] => match self.parsed.as_ref().and_then(|parsed| parsed.query(arguments)) {
+                Some(answer) => answer.unwrap_or_else(|message| format!("error: {message}")),
+                None => self.get_missing_model(),
+            },
+            _ => format!("error: unknown command: {line}, try help"),
+        };
+
+        Step::Print(text)
+    }
+
+    /// Reads the input again and parses it, keeping the previous model when that fails.
+    fn load(&mut self) -> String {
+        let text = match read_source(&self.source) {
+            Ok(text) => text,
+            Err(error) => return format!("error: {error}"),
+        };
+
+        match self.puzzle.parse(&text) {
+            Ok(parsed) => {
+                self.text = text;
+                self.parsed = Some(parsed);
+                format!("loaded {}, {} line(s)", self.source, self.text.lines().count())
+            }
+            Err(error) => {
+                let kept = if self.parsed.is_some() { "\nkeeping the previous input" } else { "" };
+                format!("error: {}{kept}", error.with_file(&self.source))
+            }
+        }
+    }
+
+    fn solve(&self, parts: &[Part]) -> String {
+        if self.parsed.is_none() {
+            return self.get_missing_model();
+        }
+
+        get_records(self.puzzle, &self.text, parts, &self.source)
+            .iter()
+            .map(|record| match (&record.answer, &record.error) {
+                (Some(answer), _) => format!(
+                    "part {}: {answer} in {} (parsing {})",
+                    record.part,
+                    format_duration(record.duration),
+                    format_duration(record.parse_duration)
+                ),
+                (None, error) => format!("part {}: error: {}", record.part, error.as_deref().unwrap_or("no answer")),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn get_help(&self) -> String {
+        let day = self.puzzle.get_day();
+
+        match self.puzzle.get_query_usage() {
+            Some(query) => format!("{HELP}\n\nday {day}:\n  {query}"),
+            None => format!("{HELP}\n\nday {day} has no queries yet"),
+        }
+    }
+
+    fn get_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(index, command)| format!("{:>4}  {command}", index + 1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn get_missing_model(&self) -> String {
+        match self.parsed.is_none() {
+            true => "error: no input loaded, fix it and reload".to_string(),
+            false => format!("error: day {} has no model to explore", self.puzzle.get_day()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Cursor;
+
+    use crate::testing::create_temp_dir;
+
+    fn get_example_path(day: u8) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("../day{day:02}/examples/example_0.txt"))
+    }
+
+    fn create_session(day: u8) -> Session {
+        let mut session = Session::new(get_registry().get(day).unwrap(), InputSource::Path(get_example_path(day)));
+        assert!(session.load().starts_with("loaded "));
+        session
+    }
+
+    fn execute(session: &mut Session, commands: &str) -> String {
+        let mut output = vec![];
+        session.run(Cursor::new(commands), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn query(day: u8, line: &str) -> String {
+        match create_session(day).handle(line) {
+            Step::Print(text) => text,
+            Step::Quit => panic!("{line} quit"),
+        }
+    }
+
+    #[test]
+    fn queries() {
+        assert_eq!(query(1, "query 2"), "\"pqr3stu8vwx\"\ndigits only: 38\nspelled out too: 38");
+        assert!(query(2, "query 3").ends_with("smallest bag: 20 red, 13 green, 6 blue, power 1560\npossible with 12 red, 13 green and 14 blue: false"));
+        assert_eq!(query(3, "query 3 1"), "(3, 1): symbol *, next to 467, 35");
+        assert_eq!(query(3, "query 2 2"), "(2, 2): number 35 (uid 3)");
+        assert_eq!(query(3, "inspect"), "10x10 schematic, 10 number(s), 6 symbol(s) of which 3 `*`");
+        assert!(query(4, "query 3").ends_with("matching: 21 1, 2 point(s)\ncopies: 4"));
+        assert_eq!(
+            query(5, "query 79"),
+            "seed 79\nsoil 81 (by 52 50 48)\nfertilizer 81 (unmapped)\nwater 81 (unmapped)\nlight 74 (by 18 25 70)\n\
+             temperature 78 (by 68 64 13)\nhumidity 78 (unmapped)\nlocation 82 (by 60 56 37)"
+        );
+        assert_eq!(query(6, "query joined"), "71530ms with a record of 940200mm, 71503 way(s) to win");
+        assert_eq!(query(6, "query 2"), "15ms with a record of 40mm, 8 way(s) to win");
+    }
+
+    #[test]
+    fn bad_queries() {
+        assert_eq!(query(3, "query 10 0"), "error: (10, 0) is outside the 10x10 schematic");
+        assert_eq!(query(3, "query 1"), "error: missing y");
+        assert_eq!(query(4, "query 7"), "error: no card 7, there are 6");
+        assert_eq!(query(5, "query seed"), "error: invalid seed: seed");
+        assert_eq!(query(5, "fly"), "error: unknown command: fly, try help");
+        assert_eq!(query(5, "run 3"), "error: there is no part 3");
+    }
+
+    #[test]
+    fn runs_and_history() {
+        let mut session = create_session(5);
+
+        let output = execute(&mut session, "run 1\n\nhistory\n!1\n!9\nquit\nrun 2\n");
+
+        assert!(output.starts_with("day05> part 1: 35 in "), "{output}");
+        assert!(output.contains("day05> day05>    1  run 1\n   2  history\nday05> run 1\npart 1: 35 in "), "{output}");
+        assert!(output.contains("day05> error: no command !9 in the history\nday05> "), "{output}");
+        assert!(!output.contains("part 2"), "{output}");
+        assert_eq!(session.history, vec!["run 1", "history", "run 1", "quit"]);
+    }
+
+    #[test]
+    fn reloads() {
+        let dir = create_temp_dir("repl");
+        let path = dir.join("day06.txt");
+        fs::write(&path, "Time: 7\nDistance: 9").unwrap();
+
+        let mut session = Session::new(get_registry().get(6).unwrap(), InputSource::Path(path.clone()));
+        assert_eq!(session.load(), format!("loaded {}, 2 line(s)", path.display()));
+
+        fs::write(&path, "Time: 7 15").unwrap();
+        let output = execute(&mut session, "reload\nquery 1\n");
+        assert!(output.contains("expected a `Distance: <numbers>` line after this one"), "{output}");
+        assert!(output.contains("keeping the previous input\nday06> 7ms with a record of 9mm, 4 way(s) to win"), "{output}");
+
+        fs::write(&path, "Time: 7 15\nDistance: 9 40").unwrap();
+        let output = execute(&mut session, "reload\nquery 2\nrun 2\n");
+        assert!(output.contains("day06> 15ms with a record of 40mm, 8 way(s) to win\nday06> part 2: 712 in "), "{output}");
+    }
+}
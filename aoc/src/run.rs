@@ -0,0 +1,120 @@
+use aoc_common::{format_duration, read_source, Args, InputResolver, Part, Puzzle, Report};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+use crate::table::{Align, Table};
+
+pub const USAGE: &str = "aoc run <day> [--part <1|2>] [--input <path | ->] [--profile <name>]
+aoc run --all [--part <1|2>] [--profile <name>]";
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let all = args.flag("--all");
+    let part = get_part(&mut args)?;
+    let input = args.option("--input")?;
+    let resolver = get_resolver(&mut args)?;
+    let day = args.parsed_positional::<u8>("day")?;
+    args.finish()?;
+
+    let registry = get_registry();
+
+    let puzzles: Vec<&dyn Puzzle> = match (day, all) {
+        (Some(day), false) => vec![registry.get(day).ok_or(Error::UnknownDay(day))?],
+        (None, true) if input.is_none() => registry.iter().collect(),
+        _ => return Err(Error::Usage(format!("usage:\n{USAGE}"))),
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let mut reports = vec![];
+
+    for puzzle in puzzles {
+        let source = resolver.resolve(puzzle.get_day(), input.as_deref())?;
+        let text = read_source(&source)?;
+
+        reports.push(aoc_common::run(puzzle, &text, &parts).map_err(|error| error.with_file(&source))?);
+    }
+
+    print!("{}", create_table(&reports));
+
+    Ok(())
+}
+
+pub fn get_part(args: &mut Args) -> Result<Option<Part>> {
+    match args.parsed_option::<u8>("--part")? {
+        Some(number) => Part::try_from(number)
+            .map(Some)
+            .map_err(|number| Error::Usage(format!("there is no part {number}"))),
+        None => Ok(None),
+    }
+}
+
+/// The resolver from the environment, switched to `--profile` when given.
+pub fn get_resolver(args: &mut Args) -> Result<InputResolver> {
+    let resolver = InputResolver::from_env();
+
+    Ok(match args.option("--profile")? {
+        Some(profile) => resolver.with_profile(&profile),
+        None => resolver,
+    })
+}
+
+fn create_table(reports: &[Report]) -> Table {
+    let mut table = Table::new(&[
+        ("Day", Align::Right),
+        ("Title", Align::Left),
+        ("Part", Align::Right),
+        ("Answer", Align::Left),
+        ("Time", Align::Right),
+    ]);
+
+    for report in reports {
+        table.push(vec![
+            report.day.to_string(),
+            report.title.to_string(),
+            "parse".to_string(),
+            String::new(),
+            format_duration(report.parse_duration),
+        ]);
+
+        for part in &report.parts {
+            table.push(vec![
+                report.day.to_string(),
+                report.title.to_string(),
+                part.part.to_string(),
+                part.answer.to_string(),
+                format_duration(part.duration),
+            ]);
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_single_part() {
+        let registry = get_registry();
+        let puzzle = registry.get(6).unwrap();
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+        let report = aoc_common::run(puzzle, input, &[Part::One]).unwrap();
+        let table = create_table(&[report]).to_string();
+
+        assert!(table.contains("Wait For It"));
+        assert!(table.contains("288"));
+        assert!(!table.contains("71503"));
+    }
+
+    #[test]
+    fn part_option() {
+        let mut args = Args::new(["--part".to_string(), "3".to_string()]);
+
+        assert!(get_part(&mut args).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::{get_records, Args, Json, Part, Puzzle, Record};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+use crate::http::{read_request, write_response, Request, Response};
+
+pub const USAGE: &str = "aoc serve [--host <address>] [--port <n>] [--max-body <bytes>]";
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 2023;
+const DEFAULT_MAX_BODY: usize = 16 * 1024 * 1024;
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let host = args.option("--host")?.unwrap_or_else(|| DEFAULT_HOST.to_string());
+    let port = args.parsed_option::<u16>("--port")?.unwrap_or(DEFAULT_PORT);
+    let max_body = args.parsed_option::<usize>("--max-body")?.unwrap_or(DEFAULT_MAX_BODY);
+    args.finish()?;
+
+    let listener = TcpListener::bind((host.as_str(), port))
+        .map_err(|error| Error::Http(format!("cannot listen on {host}:{port}: {error}")))?;
+    let address = listener
+        .local_addr()
+        .map_err(|error| Error::Http(format!("cannot listen on {host}:{port}: {error}")))?;
+
+    // With --port 0 this line is the only way to learn the port, so it goes out before serving.
+    println!("listening on http://{address}");
+
+    serve(listener, max_body);
+
+    Ok(())
+}
+
+/// Answers every connection on its own thread, so a slow part never blocks `GET /days`.
+pub fn serve(listener: TcpListener, max_body: usize) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+
+        thread::spawn(move || answer(stream, max_body));
+    }
+}
+
+fn answer(mut stream: TcpStream, max_body: usize) {
+    let _ = stream.set_read_timeout(Some(TIMEOUT));
+    let _ = stream.set_write_timeout(Some(TIMEOUT));
+
+    let response = match read_request(&mut stream, max_body) {
+        Ok(request) => {
+            let response = handle(&request);
+            aoc_common::info!("{} {} -> {}", request.method, request.target, response.status);
+            response
+        }
+        Err(error) => get_error(400, &error.to_string()),
+    };
+
+    let _ = write_response(&mut stream, &response, "application/json");
+}
+
+/// Routes a request to the solvers:
+///
+/// - `GET /days` lists every registered day and its title.
+/// - `POST /day/<n>/part/<p>` solves one part of the puzzle text in the body.
+/// - `POST /day/<n>` solves both parts.
+///
+/// Answers are the records of `--format json`, with status 422 when the input did not parse or
+/// a part panicked.
+pub fn handle(request: &Request) -> Response {
+    let path = request.target.split('?').next().unwrap_or("");
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match segments[..] {
+        ["days"] => match request.method.as_str() {
+            "GET" => Response::new(200, get_days().to_string()),
+            _ => get_error(405, "expected GET"),
+        },
+        ["day", day] => solve(request, day, &Part::ALL),
+        ["day", day, "part", part] => match part.parse::<u8>().ok().and_then(|part| Part::try_from(part).ok()) {
+            Some(part) => solve(request, day, &[part]),
+            None => get_error(404, &format!("no part {part}, expected 1 or 2")),
+        },
+        _ => get_error(404, &format!("no route for {path}")),
+    }
+}
+
+fn solve(request: &Request, day: &str, parts: &[Part]) -> Response {
+    let Some(puzzle) = day.parse::<u8>().ok().and_then(|day| get_registry().get(day)) else {
+        return get_error(404, &format!("day {day} is not registered"));
+    };
+
+    if request.method != "POST" {
+        return get_error(405, "expected POST with the puzzle input as the body");
+    }
+
+    let records = get_records(puzzle, &request.body, parts, "request");
+    let status = if records.iter().all(Record::is_success) { 200 } else { 422 };
+
+    let body = match &records[..] {
+        [record] => record.to_json(),
+        records => Json::Array(records.iter().map(Record::to_json).collect()),
+    };
+
+    Response::new(status, body.to_string())
+}
+
+fn get_days() -> Json {
+    Json::Array(
+        get_registry()
+            .iter()
+            .map(|puzzle: &dyn Puzzle| {
+                Json::object([("day", puzzle.get_day().into()), ("title", puzzle.get_title().into())])
+            })
+            .collect(),
+    )
+}
+
+fn get_error(status: u16, message: &str) -> Response {
+    Response::new(status, Json::object([("error", message.into())]).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::send;
+
+    const EXAMPLE: &str = include_str!("../../day05/examples/example_0.txt");
+
+    fn post(target: &str, body: &str) -> Response {
+        handle(&Request::new("POST", target).body(body.to_string()))
+    }
+
+    #[test]
+    fn routes() {
+        let days = handle(&Request::new("GET", "/days"));
+        assert_eq!(days.status, 200);
+        assert!(days.body.starts_with("[{\"day\":1,\"title\":\"Trebuchet?!\"},"));
+
+        let part = post("/day/5/part/1", EXAMPLE);
+        assert_eq!(part.status, 200);
+        assert!(part.body.starts_with("{\"day\":5,\"title\":\"If You Give A Seed A Fertilizer\",\"part\":1,\"answer\":35,"));
+
+        let both = post("/day/5", EXAMPLE);
+        assert_eq!(both.status, 200);
+        assert!(both.body.starts_with('[') && both.body.contains("\"answer\":46,"));
+
+        assert_eq!(post("/days", "").status, 405);
+        assert_eq!(handle(&Request::new("GET", "/day/5/part/1")).status, 405);
+        assert_eq!(post("/day/26/part/1", EXAMPLE).status, 404);
+        assert_eq!(post("/day/5/part/3", EXAMPLE).status, 404);
+        assert_eq!(post("/day/five", EXAMPLE).status, 404);
+        assert_eq!(handle(&Request::new("GET", "/")).body, "{\"error\":\"no route for /\"}");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let response = post("/day/2/part/2", "Game 1: 3 blue\nGame 2: 1 purple");
+
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains("\"answer\":null,"));
+        assert!(response.body.contains(
+            "\"success\":false,\"error\":\"request:2:11: unsupported colour, expected red, green or blue\\n"
+        ));
+    }
+
+    #[test]
+    fn serves_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || serve(listener, 64));
+
+        let days = send(&Request::new("GET", &format!("{url}/days"))).unwrap();
+        assert_eq!(days.status, 200);
+        assert!(days.body.contains("\"title\":\"Wait For It\""));
+
+        let race = "Time: 7\nDistance: 9";
+        let part = send(&Request::new("POST", &format!("{url}/day/6/part/1")).body(race.to_string())).unwrap();
+        assert_eq!(part.status, 200);
+        assert!(part.body.contains("\"answer\":4,"));
+
+        let large = send(&Request::new("POST", &format!("{url}/day/6/part/1")).body("Time: 7\n".repeat(10))).unwrap();
+        assert_eq!(large.status, 400);
+        assert_eq!(large.body, "{\"error\":\"body of 80 bytes is over the limit of 64\"}");
+    }
+}
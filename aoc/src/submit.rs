@@ -0,0 +1,207 @@
+use aoc_common::{read_source, Answer, Args, Part};
+use aoc2023::get_registry;
+
+use crate::client::{get_unix_time, Client};
+use crate::error::{Error, Result};
+use crate::journal::{Entry, Journal, Verdict, JOURNAL_FILE};
+use crate::run::get_resolver;
+
+pub const USAGE: &str = "aoc submit <day> <part> [--input <path | ->] [--profile <name>] [--base-url <url>] [--session <token>]";
+
+const DEFAULT_COOLDOWN: u64 = 60;
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let input = args.option("--input")?;
+    let resolver = get_resolver(&mut args)?;
+    let state_dir = resolver.get_input_dir();
+    let client = Client::from_args(&mut args, &state_dir)?;
+
+    let day = args.parsed_positional::<u8>("day")?;
+    let part = args.parsed_positional::<u8>("part")?;
+    args.finish()?;
+
+    let (Some(day), Some(part)) = (day, part) else {
+        return Err(Error::Usage(format!("usage:\n{USAGE}")));
+    };
+    let part = Part::try_from(part).map_err(|part| Error::Usage(format!("there is no part {part}")))?;
+
+    let puzzle = get_registry().get(day).ok_or(Error::UnknownDay(day))?;
+    let source = resolver.resolve(day, input.as_deref())?;
+    let text = read_source(&source)?;
+    let answer = match puzzle.parse(&text).map_err(|error| error.with_file(&source))?.solve(part) {
+        Answer::Unsolved(reason) => return Err(Error::Usage(format!("day {day} part {part} has no answer to submit: {reason}"))),
+        answer => answer.to_string(),
+    };
+
+    // Each profile is a different account, with its own submissions.
+    let mut journal = Journal::load(&resolver.get_profile_dir().join(JOURNAL_FILE))?;
+
+    println!("day {day} part {part}: submitting {answer}");
+
+    let verdict = submit(&client, &mut journal, day, part, &answer, get_unix_time().as_secs())?;
+
+    println!("day {day} part {part}: {verdict}");
+
+    Ok(())
+}
+
+/// Sends an answer unless the journal already shows it cannot be right, and records the verdict.
+pub fn submit(client: &Client, journal: &mut Journal, day: u8, part: Part, answer: &str, now: u64) -> Result<Verdict> {
+    if answer.is_empty() || answer.contains(['\t', '\n', '\r']) {
+        return Err(Error::Usage(format!("cannot submit {answer:?}")));
+    }
+
+    journal.check(day, part, answer, now).map_err(Error::Refused)?;
+
+    let level = part.to_string();
+    let response = client.post_form(&format!("/day/{day}/answer"), &[("level", &level), ("answer", answer)])?;
+
+    if !response.is_success() {
+        return Err(Error::Http(format!("submitting failed with status {}: {}", response.status, response.body.trim())));
+    }
+
+    let (verdict, cooldown) = parse_response(&response.body);
+
+    journal.append(Entry {
+        time: now,
+        day,
+        part,
+        verdict,
+        cooldown_until: now + cooldown,
+        answer: answer.to_string(),
+    })?;
+
+    Ok(verdict)
+}
+
+/// Reads the verdict and the number of seconds to wait before the next submission.
+pub fn parse_response(body: &str) -> (Verdict, u64) {
+    let text = body.to_lowercase().replace('’', "'");
+
+    if text.contains("that's the right answer") {
+        return (Verdict::Correct, 0);
+    }
+
+    if text.contains("you gave an answer too recently") {
+        return (Verdict::Wait, parse_time_left(&text).unwrap_or(DEFAULT_COOLDOWN));
+    }
+
+    if text.contains("not the right answer") {
+        let verdict = if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+
+        return (verdict, parse_retry_delay(&text).unwrap_or(DEFAULT_COOLDOWN));
+    }
+
+    if text.contains("don't seem to be solving the right level") {
+        return (Verdict::AlreadySolved, 0);
+    }
+
+    (Verdict::Unknown, 0)
+}
+
+// "you have 1m 5s left to wait"
+fn parse_time_left(text: &str) -> Option<u64> {
+    let start = text.rfind("you have ")? + "you have ".len();
+    let end = start + text[start..].find(" left")?;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (number, unit) = token.split_at(token.len().checked_sub(1)?);
+            let number = number.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+// "please wait one minute before trying again", "please wait 5 minutes before trying again"
+fn parse_retry_delay(text: &str) -> Option<u64> {
+    let start = text.find("please wait ")? + "please wait ".len();
+    let mut words = text[start..].split_whitespace();
+
+    let count = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse::<u64>().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(count * 60),
+        unit if unit.starts_with("second") => Some(count),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::RateLimiter;
+    use crate::http::Response;
+    use crate::journal::Refusal;
+    use crate::testing::{create_temp_dir, MockServer};
+    use std::fs;
+    use std::time::Duration;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 30s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to restoring snow operations.</p></article>";
+
+    #[test]
+    fn responses() {
+        assert_eq!(parse_response(TOO_HIGH), (Verdict::TooHigh, 60));
+        assert_eq!(parse_response(TOO_RECENT), (Verdict::Wait, 270));
+        assert_eq!(parse_response(CORRECT), (Verdict::Correct, 0));
+        assert_eq!(parse_response("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again."), (Verdict::TooLow, 300));
+        assert_eq!(parse_response("That's not the right answer.  Please wait one minute before trying again."), (Verdict::Wrong, 60));
+        assert_eq!(parse_response("You don't seem to be solving the right level.  Did you already complete it?"), (Verdict::AlreadySolved, 0));
+        assert_eq!(parse_response("<html>maintenance</html>"), (Verdict::Unknown, 0));
+    }
+
+    #[test]
+    fn submissions_are_journaled_and_guarded() {
+        let dir = create_temp_dir("submit-guard");
+        let server = MockServer::start(|request| match request.body.as_str() {
+            "level=2&answer=7873084" => Response::new(200, CORRECT.to_string()),
+            _ => Response::new(200, TOO_HIGH.to_string()),
+        });
+        let client = Client::new(&server.get_url(), Some("secret".to_string()), RateLimiter::new(&dir, Duration::ZERO));
+        let mut journal = Journal::load(&dir.join(JOURNAL_FILE)).unwrap();
+
+        assert_eq!(submit(&client, &mut journal, 5, Part::Two, "9000000", 1000).unwrap(), Verdict::TooHigh);
+
+        let refused = |result: Result<Verdict>| match result {
+            Err(Error::Refused(refusal)) => refusal,
+            _ => panic!("expected a refusal"),
+        };
+
+        assert_eq!(refused(submit(&client, &mut journal, 5, Part::Two, "7873084", 1030)), Refusal::Cooldown(30));
+        assert_eq!(refused(submit(&client, &mut journal, 5, Part::Two, "9000000", 2000)), Refusal::AlreadyRejected(Verdict::TooHigh));
+        assert_eq!(refused(submit(&client, &mut journal, 5, Part::Two, "9500000", 2000)), Refusal::AtOrAboveTooHigh(9000000));
+        assert_eq!(server.get_requests().len(), 1);
+
+        assert_eq!(submit(&client, &mut journal, 5, Part::Two, "7873084", 2000).unwrap(), Verdict::Correct);
+        assert_eq!(refused(submit(&client, &mut journal, 5, Part::Two, "7873084", 3000)), Refusal::AlreadySolved("7873084".to_string()));
+        assert_eq!(server.get_requests().len(), 2);
+
+        let journal = Journal::load(&dir.join(JOURNAL_FILE)).unwrap();
+        let verdicts: Vec<Verdict> = journal.get_entries().iter().map(|entry| entry.verdict).collect();
+        assert_eq!(verdicts, vec![Verdict::TooHigh, Verdict::Correct]);
+        assert_eq!(journal.get_entries()[0].cooldown_until, 1060);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
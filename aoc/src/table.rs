@@ -0,0 +1,85 @@
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Plain text table with columns sized to their widest cell.
+pub struct Table {
+    columns: Vec<(&'static str, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&'static str, Align)]) -> Self {
+        Table {
+            columns: columns.to_vec(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    fn get_widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, (header, _))| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(index))
+                    .map(|cell| cell.chars().count())
+                    .fold(header.chars().count(), std::cmp::max)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.get_widths();
+
+        let write_row = |f: &mut fmt::Formatter<'_>, cells: Vec<&str>| -> fmt::Result {
+            let line: Vec<String> = cells
+                .iter()
+                .zip(&self.columns)
+                .zip(&widths)
+                .map(|((cell, (_, align)), width)| match align {
+                    Align::Left => format!("{cell:<width$}"),
+                    Align::Right => format!("{cell:>width$}"),
+                })
+                .collect();
+
+            writeln!(f, "{}", line.join("  ").trim_end())
+        };
+
+        write_row(f, self.columns.iter().map(|(header, _)| *header).collect())?;
+
+        let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        write_row(f, separators.iter().map(|separator| separator.as_str()).collect())?;
+
+        for row in &self.rows {
+            write_row(f, row.iter().map(|cell| cell.as_str()).collect())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_are_aligned() {
+        let mut table = Table::new(&[("Day", Align::Right), ("Answer", Align::Left)]);
+        table.push(vec!["1".to_string(), "54331".to_string()]);
+        table.push(vec!["12".to_string(), "7".to_string()]);
+
+        assert_eq!(table.to_string(), "Day  Answer\n---  ------\n  1  54331\n 12  7\n");
+    }
+}
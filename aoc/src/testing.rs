@@ -0,0 +1,86 @@
+use std::env;
+use std::fs;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::path::PathBuf;
+use std::thread::{self, JoinHandle};
+
+use crate::http::{read_request, write_response, Request, Response};
+
+/// Local stand-in for the puzzle website, recording every request it receives.
+pub struct MockServer {
+    address: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(vec![]));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let handle = {
+            let requests = Arc::clone(&requests);
+            let running = Arc::clone(&running);
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    let Ok(mut stream) = stream else {
+                        continue;
+                    };
+
+                    if let Ok(request) = read_request(&mut stream, usize::MAX) {
+                        let response = handler(&request);
+                        requests.lock().unwrap().push(request);
+                        let _ = write_response(&mut stream, &response, "text/plain");
+                    }
+                }
+            })
+        };
+
+        MockServer {
+            address,
+            requests,
+            running,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn get_url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    pub fn get_requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        let _ = TcpStream::connect(self.address);
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Fresh, empty directory under the system temp dir, unique to this test process.
+pub fn create_temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,435 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_common::{is_profile_name, Answers, Args, Expectation, InputResolver, Part, Registry, PUZZLE_INPUT};
+use aoc2023::get_registry;
+
+use crate::error::{Error, Result};
+use crate::run::get_part;
+use crate::table::{Align, Table};
+
+pub const USAGE: &str = "aoc verify [<day>] [--part <1|2>] [--profile <name>] [--matrix] [--slow]";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    Mismatch,
+    Missing,
+    NoInput,
+    NoKey,
+    Invalid,
+    Panicked,
+    UnknownDay,
+    Skipped,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Status::Ok | Status::Skipped | Status::NoKey)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Status::Ok => "ok",
+            Status::Mismatch => "MISMATCH",
+            Status::Missing => "MISSING",
+            Status::NoInput => "NO INPUT",
+            Status::NoKey => "skipped (no key)",
+            Status::Invalid => "PARSE ERROR",
+            Status::Panicked => "PANIC",
+            Status::UnknownDay => "UNKNOWN DAY",
+            Status::Skipped => "skipped (slow)",
+        };
+
+        write!(f, "{text}")
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub profile: String,
+    pub part: Part,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub status: Status,
+}
+
+pub struct Filter {
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub profile: Option<String>,
+    pub slow: bool,
+    pub matrix: bool,
+}
+
+pub fn execute(mut args: Args) -> Result<()> {
+    let part = get_part(&mut args)?;
+    let slow = args.flag("--slow");
+    let matrix = args.flag("--matrix");
+    let profile = args.option("--profile")?;
+    let day = args.parsed_positional::<u8>("day")?;
+    args.finish()?;
+
+    if let Some(profile) = profile.as_ref().filter(|profile| !is_profile_name(profile)) {
+        return Err(Error::Usage(format!("invalid profile {profile:?}")));
+    }
+
+    let answers = Answers::find()?;
+    let filter = Filter { day, part, profile, slow, matrix };
+
+    let checks = verify(&get_registry(), &answers, &InputResolver::from_env(), &filter);
+
+    print!("{}", create_table(&checks));
+
+    let failures = checks.iter().filter(|check| check.status.is_failure()).count();
+    if failures > 0 {
+        return Err(Error::Verify(failures));
+    }
+
+    Ok(())
+}
+
+/// Runs every expectation in `answers` and reports registered days that have none.
+///
+/// In matrix mode every registered day also runs against every profile with an input for
+/// it, so a solver that only works on one account's input cannot hide behind missing answers.
+pub fn verify(registry: &Registry, answers: &Answers, resolver: &InputResolver, filter: &Filter) -> Vec<Check> {
+    let parts: Vec<Part> = Part::ALL
+        .into_iter()
+        .filter(|part| filter.part.is_none_or(|filter| filter == *part))
+        .collect();
+    let is_selected = |day: u8| filter.day.is_none_or(|filter| filter == day);
+    let is_selected_profile = |profile: &str| filter.profile.as_deref().is_none_or(|filter| filter == profile);
+
+    let mut checks = vec![];
+
+    for (day, profile, expectation) in answers.iter() {
+        if !is_selected(day) || !is_selected_profile(profile) {
+            continue;
+        }
+
+        let parts: Vec<Part> = parts
+            .iter()
+            .copied()
+            .filter(|part| filter.matrix || expectation.answers.contains_key(part))
+            .collect();
+
+        checks.extend(check_profile(registry, resolver, filter, &parts, day, profile, expectation));
+    }
+
+    if filter.matrix {
+        for profile in resolver.get_profiles().iter().filter(|profile| is_selected_profile(profile)) {
+            let profile_resolver = resolver.clone().with_profile(profile);
+
+            for puzzle in registry.iter().filter(|puzzle| is_selected(puzzle.get_day())) {
+                let day = puzzle.get_day();
+
+                if answers.get(day, profile).is_some() || profile_resolver.resolve(day, None).is_err() {
+                    continue;
+                }
+
+                // Slowness belongs to the solver, so the default profile's list applies everywhere.
+                let expectation = Expectation {
+                    slow: answers.get(day, PUZZLE_INPUT).map(|expectation| expectation.slow.clone()).unwrap_or_default(),
+                    ..Expectation::default()
+                };
+
+                checks.extend(check_profile(registry, resolver, filter, &parts, day, profile, &expectation));
+            }
+        }
+    }
+
+    if is_selected_profile(PUZZLE_INPUT) {
+        for puzzle in registry.iter().filter(|puzzle| is_selected(puzzle.get_day())) {
+            let day = puzzle.get_day();
+
+            for &part in &parts {
+                let is_covered = checks.iter().any(|check| check.day == day && check.part == part)
+                    || answers
+                        .iter()
+                        .any(|(expected_day, _, expectation)| expected_day == day && expectation.answers.contains_key(&part));
+
+                if !is_covered {
+                    checks.push(Check {
+                        day,
+                        profile: PUZZLE_INPUT.to_string(),
+                        part,
+                        expected: None,
+                        actual: None,
+                        status: Status::Missing,
+                    });
+                }
+            }
+        }
+    }
+
+    checks.sort_by(|a, b| (a.day, &a.profile, a.part).cmp(&(b.day, &b.profile, b.part)));
+
+    checks
+}
+
+fn check_profile(
+    registry: &Registry,
+    resolver: &InputResolver,
+    filter: &Filter,
+    parts: &[Part],
+    day: u8,
+    profile: &str,
+    expectation: &Expectation,
+) -> Vec<Check> {
+    let check = |part: Part, actual: Option<String>, status: Status| Check {
+        day,
+        profile: profile.to_string(),
+        part,
+        expected: expectation.answers.get(&part).cloned(),
+        actual,
+        status,
+    };
+
+    let Some(puzzle) = registry.get(day) else {
+        return parts.iter().map(|&part| check(part, None, Status::UnknownDay)).collect();
+    };
+
+    let (slow, parts): (Vec<Part>, Vec<Part>) = parts
+        .iter()
+        .partition(|part| !filter.slow && expectation.slow.contains(part));
+
+    let mut checks: Vec<Check> = slow.iter().map(|&part| check(part, None, Status::Skipped)).collect();
+
+    if parts.is_empty() {
+        return checks;
+    }
+
+    let text = match resolver.clone().with_profile(profile).read(day, None) {
+        Ok(text) => text,
+        Err(error) => {
+            let status = if error.is_missing_key() { Status::NoKey } else { Status::NoInput };
+            checks.extend(parts.iter().map(|&part| check(part, None, status)));
+            return checks;
+        }
+    };
+
+    // Another account's input may break a solver badly enough to panic; report it like a mismatch.
+    let report = match panic::catch_unwind(AssertUnwindSafe(|| aoc_common::run(puzzle, &text, &parts))) {
+        Ok(Ok(report)) => report,
+        Ok(Err(error)) => {
+            eprintln!("day {day} {profile}: {error}");
+            checks.extend(parts.iter().map(|&part| check(part, None, Status::Invalid)));
+            return checks;
+        }
+        Err(_) => {
+            checks.extend(parts.iter().map(|&part| check(part, None, Status::Panicked)));
+            return checks;
+        }
+    };
+
+    for part in report.parts {
+        let actual = part.answer.to_string();
+        let status = match expectation.answers.get(&part.part) {
+            Some(expected) if *expected == actual => Status::Ok,
+            Some(_) => Status::Mismatch,
+            None => Status::Missing,
+        };
+
+        checks.push(check(part.part, Some(actual), status));
+    }
+
+    checks
+}
+
+fn create_table(checks: &[Check]) -> Table {
+    let mut table = Table::new(&[
+        ("Day", Align::Right),
+        ("Profile", Align::Left),
+        ("Part", Align::Right),
+        ("Expected", Align::Left),
+        ("Actual", Align::Left),
+        ("Status", Align::Left),
+    ]);
+
+    for check in checks {
+        table.push(vec![
+            check.day.to_string(),
+            check.profile.clone(),
+            check.part.to_string(),
+            check.expected.clone().unwrap_or_default(),
+            check.actual.clone().unwrap_or_default(),
+            check.status.to_string(),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::create_temp_dir;
+    use aoc_common::{Answer, ParseError, Solution};
+    use std::fs;
+    use std::path::Path;
+
+    const ANSWERS: &str = "[day06.puzzle]
+part_one = 288
+part_two = 1
+
+[day06.other]
+part_one = 352
+
+[day05.puzzle]
+part_one = 35
+part_two = 46
+slow = [\"part_two\"]
+
+[day24.puzzle]
+part_one = 1
+";
+
+    fn get_statuses(checks: &[Check]) -> Vec<(u8, &str, Part, Status)> {
+        checks
+            .iter()
+            .map(|check| (check.day, check.profile.as_str(), check.part, check.status))
+            .collect()
+    }
+
+    #[test]
+    fn statuses() {
+        let dir = create_temp_dir("verify-statuses");
+        fs::create_dir_all(dir.join("other")).unwrap();
+        fs::write(dir.join("day06.txt"), "Time:      7  15   30\nDistance:  9  40  200").unwrap();
+        fs::write(dir.join("other").join("day06.txt"), "Time:      30\nDistance:  200").unwrap();
+
+        let answers = Answers::parse(ANSWERS, Path::new("answers.toml")).unwrap();
+        let resolver = InputResolver::new(Some(dir.clone()), dir.clone());
+        let filter = Filter {
+            day: None,
+            part: None,
+            profile: None,
+            slow: false,
+            matrix: false,
+        };
+
+        let checks = verify(&get_registry(), &answers, &resolver, &filter);
+        let statuses = get_statuses(&checks);
+
+        assert!(statuses.contains(&(6, "puzzle", Part::One, Status::Ok)));
+        assert!(statuses.contains(&(6, "puzzle", Part::Two, Status::Mismatch)));
+        assert!(statuses.contains(&(6, "other", Part::One, Status::Mismatch)));
+        assert!(statuses.contains(&(5, "puzzle", Part::One, Status::NoInput)));
+        assert!(statuses.contains(&(5, "puzzle", Part::Two, Status::Skipped)));
+        assert!(statuses.contains(&(24, "puzzle", Part::One, Status::UnknownDay)));
+        assert!(statuses.contains(&(1, "puzzle", Part::One, Status::Missing)));
+        assert!(statuses.contains(&(4, "puzzle", Part::Two, Status::Missing)));
+
+        let mismatch = checks.iter().find(|check| check.day == 6 && check.part == Part::Two).unwrap();
+        assert_eq!(mismatch.actual.as_deref(), Some("71503"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn filtered() {
+        let answers = Answers::parse(ANSWERS, Path::new("answers.toml")).unwrap();
+        let resolver = InputResolver::new(Some(create_temp_dir("verify-filtered")), std::env::temp_dir());
+        let filter = Filter {
+            day: Some(5),
+            part: Some(Part::Two),
+            profile: None,
+            slow: false,
+            matrix: false,
+        };
+
+        let checks = verify(&get_registry(), &answers, &resolver, &filter);
+
+        assert_eq!(get_statuses(&checks), vec![(5, "puzzle", Part::Two, Status::Skipped)]);
+    }
+
+    /// Only understands inputs whose lines all have the length of the first one, like a grid.
+    struct Grid;
+
+    impl Solution for Grid {
+        const DAY: u8 = 3;
+        const TITLE: &'static str = "Grid";
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> std::result::Result<Self::Input, ParseError> {
+            Ok(input.lines().map(|line| line.to_string()).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Answer {
+            assert!(input.iter().all(|line| line.len() == input[0].len()), "ragged grid");
+            input.len().into()
+        }
+
+        fn part_two(input: &Self::Input) -> Answer {
+            input.concat().len().into()
+        }
+    }
+
+    #[test]
+    fn matrix() {
+        let dir = create_temp_dir("verify-matrix");
+        fs::create_dir_all(dir.join("alice")).unwrap();
+        fs::create_dir_all(dir.join("bob")).unwrap();
+        fs::write(dir.join("day03.txt"), "ab\ncd").unwrap();
+        fs::write(dir.join("alice").join("day03.txt"), "abc\nde").unwrap();
+        fs::write(dir.join("bob").join("day03.txt"), "abc").unwrap();
+
+        let answers = Answers::parse("[day03.puzzle]\npart_one = 2\npart_two = 4\n", Path::new("answers.toml")).unwrap();
+        let registry = Registry::new(&[&Grid]);
+        let resolver = InputResolver::new(Some(dir.clone()), dir.clone());
+        let mut filter = Filter {
+            day: None,
+            part: None,
+            profile: None,
+            slow: false,
+            matrix: false,
+        };
+
+        let checks = verify(&registry, &answers, &resolver, &filter);
+        assert_eq!(get_statuses(&checks), vec![(3, "puzzle", Part::One, Status::Ok), (3, "puzzle", Part::Two, Status::Ok)]);
+
+        filter.matrix = true;
+        let checks = verify(&registry, &answers, &resolver, &filter);
+        assert_eq!(
+            get_statuses(&checks),
+            vec![
+                (3, "alice", Part::One, Status::Panicked),
+                (3, "alice", Part::Two, Status::Panicked),
+                (3, "bob", Part::One, Status::Missing),
+                (3, "bob", Part::Two, Status::Missing),
+                (3, "puzzle", Part::One, Status::Ok),
+                (3, "puzzle", Part::Two, Status::Ok),
+            ]
+        );
+        assert_eq!(checks[2].actual.as_deref(), Some("1"));
+
+        filter.profile = Some("bob".to_string());
+        let checks = verify(&registry, &answers, &resolver, &filter);
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|check| check.profile == "bob"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn answers_file_matches_solvers() {
+        let answers = Answers::find().unwrap();
+        let filter = Filter {
+            day: None,
+            part: None,
+            profile: None,
+            slow: false,
+            matrix: true,
+        };
+
+        let checks = verify(&get_registry(), &answers, &InputResolver::from_env(), &filter);
+        let failures: Vec<&Check> = checks.iter().filter(|check| check.status.is_failure()).collect();
+
+        assert!(failures.is_empty(), "{failures:?}");
+    }
+}
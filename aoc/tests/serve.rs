@@ -0,0 +1,104 @@
+//! Starts `aoc serve` on an ephemeral localhost port and talks to it over plain TCP.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["serve", "--port", "0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+
+        let address = line
+            .trim()
+            .strip_prefix("listening on http://")
+            .unwrap_or_else(|| panic!("unexpected first line: {line:?}"))
+            .to_string();
+
+        Server {
+            child,
+            address,
+        }
+    }
+
+    /// Sends one request and returns the status and body of the response.
+    fn request(&self, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.address,
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains("Content-Type: application/json"), "{head}");
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+
+        (status, body.to_string())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn lists_days() {
+    let server = Server::start();
+
+    let (status, body) = server.request("GET", "/days", "");
+
+    assert_eq!(status, 200);
+    assert!(body.starts_with("[{\"day\":1,\"title\":\"Trebuchet?!\"},{\"day\":2,"), "{body}");
+    assert_eq!(server.request("DELETE", "/days", "").0, 405);
+}
+
+#[test]
+fn solves_parts() {
+    let server = Server::start();
+    let example = include_str!("../../day01/examples/example_1.txt");
+
+    let (status, body) = server.request("POST", "/day/1/part/2", example);
+
+    assert_eq!(status, 200);
+    assert!(body.starts_with("{\"day\":1,\"title\":\"Trebuchet?!\",\"part\":2,\"answer\":281,\"type\":\"unsigned\",\"parse_ns\":"), "{body}");
+    assert!(body.contains("\"duration_ns\":") && body.ends_with("\"success\":true,\"error\":null}"), "{body}");
+
+    let (status, body) = server.request("POST", "/day/4", include_str!("../../day04/examples/example_0.txt"));
+
+    assert_eq!(status, 200);
+    assert!(body.contains("\"part\":1,\"answer\":13,") && body.contains("\"part\":2,\"answer\":30,"), "{body}");
+}
+
+#[test]
+fn reports_errors() {
+    let server = Server::start();
+
+    let (status, body) = server.request("POST", "/day/3/part/1", "467..\n...*\n..35.");
+
+    assert_eq!(status, 422);
+    assert!(body.contains("\"error\":\"request:2:1: expected 5 columns like the first row, found 4\\n"), "{body}");
+
+    assert_eq!(server.request("POST", "/day/25/part/1", "").1, "{\"error\":\"day 25 is not registered\"}");
+    assert_eq!(server.request("POST", "/day/1/part/0", "").0, 404);
+    assert_eq!(server.request("GET", "/nothing", "").0, 404);
+}
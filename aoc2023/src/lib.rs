@@ -0,0 +1,43 @@
+//! Every solved day of Advent of Code 2023 behind a single dependency.
+//!
+//! Each day is re-exported as its own module, with its model types and part functions:
+//!
+//! ```
+//! use aoc2023::common::Solution;
+//! use aoc2023::day06::{Day06, Document};
+//!
+//! let text = "Time:      7  15   30\nDistance:  9  40  200";
+//! let document: Document = text.parse().unwrap();
+//!
+//! assert_eq!(document.get_total_margin_of_error(), Some(288));
+//! assert_eq!(Day06::part_two(&Day06::parse(text).unwrap()).to_string(), "71503");
+//! ```
+
+#![warn(missing_docs)]
+
+pub use aoc_common as common;
+
+use aoc_common::Registry;
+
+// `pub use dayNN;` and `PUZZLES` for every `dayNN` dependency, which `aoc new` adds.
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Every solved day, one for each `dayNN` dependency of this crate.
+pub fn get_registry() -> Registry {
+    Registry::new(&PUZZLES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_days_registered() {
+        let registry = get_registry();
+
+        let days: Vec<u8> = registry.iter().map(|puzzle| puzzle.get_day()).collect();
+
+        assert_eq!(days, aoc_common::get_day_dependencies(include_str!("../Cargo.toml")));
+        assert!(!days.is_empty());
+    }
+}
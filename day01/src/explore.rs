@@ -0,0 +1,29 @@
+//! What `aoc repl` shows about a calibration document.
+
+use aoc_common::{get_item, Explorer};
+
+use crate::{get_calibration_value, get_text_calibration_value, Day01};
+
+pub const EXPLORER: Explorer<Day01> = Explorer {
+    usage: "query <line>            the calibration values of one line",
+    inspect: |text| inspect(text),
+    query: |text, arguments| query(text, arguments),
+};
+
+fn inspect(text: &str) -> String {
+    let lines = text.lines().count();
+    let without_digit = text.lines().filter(|line| !line.chars().any(|char| char.is_ascii_digit())).count();
+
+    format!("{lines} calibration line(s), {without_digit} without a digit")
+}
+
+fn query(text: &str, arguments: &[&str]) -> Result<String, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let line = get_item(&lines, arguments, "line")?;
+
+    Ok(format!(
+        "{line:?}\ndigits only: {}\nspelled out too: {}",
+        get_calibration_value(line),
+        get_text_calibration_value(line)
+    ))
+}
@@ -0,0 +1,66 @@
+//! Calibration documents for `aoc generate`, with each line's values read back by trying
+//! every position of it for a digit, rather than by the solver's search from both ends.
+
+use aoc_common::{get_answers, get_size, Args, Generated, Generator, Rng};
+
+pub const GENERATOR: Generator = Generator {
+    options: "--lines <n> --tokens <n>",
+    generate,
+};
+
+const DIGIT_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// The digit starting at `index`, written out too when `words` is set.
+fn get_digit_at(line: &str, index: usize, words: bool) -> Option<u64> {
+    let rest = &line[index..];
+
+    match rest.as_bytes().first() {
+        Some(byte) if byte.is_ascii_digit() => Some(u64::from(byte - b'0')),
+        _ if words => DIGIT_WORDS.iter().position(|word| rest.starts_with(word)).map(|digit| digit as u64 + 1),
+        _ => None,
+    }
+}
+
+fn get_calibration(line: &str, words: bool) -> u64 {
+    let mut digits = (0..line.len()).filter_map(|index| get_digit_at(line, index, words));
+    let first = digits.next().unwrap_or(0);
+    let last = digits.next_back().unwrap_or(first);
+
+    first * 10 + last
+}
+
+/// Lines of lowercase letters, digits and spelled out digits, each with at least one digit.
+fn generate(rng: &mut Rng, args: &mut Args) -> Result<Generated, String> {
+    let lines = get_size(args, "--lines", 1000, 1)?;
+    let tokens = get_size(args, "--tokens", 12, 1)?;
+
+    let mut input = String::new();
+    let (mut part_one, mut part_two) = (0, 0);
+
+    for _ in 0..lines {
+        let mut line = String::new();
+
+        for _ in 0..1 + rng.below(tokens) {
+            match rng.below(6) {
+                0 => line.push(char::from(b'1' + rng.below(9) as u8)),
+                1 => line.push_str(DIGIT_WORDS[rng.below(9)]),
+                _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+
+        if !line.bytes().any(|byte| byte.is_ascii_digit()) {
+            let index = rng.below(line.len() + 1);
+            line.insert(index, char::from(b'1' + rng.below(9) as u8));
+        }
+
+        part_one += get_calibration(&line, false);
+        part_two += get_calibration(&line, true);
+        input.push_str(&line);
+        input.push('\n');
+    }
+
+    Ok(Generated {
+        input,
+        answers: get_answers(Some(part_one), Some(part_two)),
+    })
+}
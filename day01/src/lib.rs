@@ -0,0 +1,206 @@
+//! Day 1: Trebuchet?!
+//!
+//! Recovers calibration values from lines of text, first from digits only and then
+//! from digits that may also be spelled out.
+
+#![warn(missing_docs)]
+
+use aoc_common::{Answer, Explorer, Generator, ParseError, Solution};
+
+mod explore;
+mod generate;
+
+/// The [`Solution`] for day 1, solving both parts straight from the text.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        aoc_common::debug!("{} calibration lines", input.lines().count());
+
+        Ok(input.to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        get_calibration_value(input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        get_text_calibration_value(input).into()
+    }
+
+    fn get_explorer() -> Option<Explorer<Self>> {
+        Some(explore::EXPLORER)
+    }
+
+    fn get_generator() -> Option<Generator> {
+        Some(generate::GENERATOR)
+    }
+}
+
+/// Part one: sums the first and last digit of every line, read as a two digit number.
+pub fn get_calibration_value(text: &str) -> u32 {
+    text.lines()
+        .map(|line| {
+            let mut digits = line.chars().filter_map(|char| char.to_digit(10));
+
+            let first_digit = digits.next().unwrap_or(0);
+            let last_digit = digits.next_back().unwrap_or(first_digit);
+
+            first_digit * 10 + last_digit
+        })
+        .sum()
+}
+
+/// Part two: like [`get_calibration_value`], but `one` to `nine` count as digits too.
+pub fn get_text_calibration_value(text: &str) -> u32 {
+    let lines = text.lines();
+
+    lines
+        .map(find_number)
+        .sum()
+}
+
+fn find_number(line: &str) -> u32 {
+    let patterns = [
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+    ];
+
+    let digit = line.chars().enumerate().find(|c| c.1.is_numeric());
+
+    let initial_result: (usize, u32) = if let Some(digit_result) = digit {
+        (digit_result.0, digit_result.1.to_string().parse::<u32>().unwrap())
+    } else {
+        (usize::MAX, 0)
+    };
+
+    let first_digit: (usize, u32) = patterns.iter()
+        .fold(initial_result, |result, pattern| {
+            if let Some(found_pattern_index) = line.find(pattern.0) {
+                if found_pattern_index < result.0 {
+                    return (found_pattern_index, pattern.1)
+                }
+            }
+
+            result
+        });
+
+    let last_real_digit = line.chars().rev().enumerate().find(|c| c.1.is_numeric());
+
+    let initial_last_result: (usize, u32) = if let Some(digit_result) = last_real_digit {
+        (line.len() - digit_result.0, digit_result.1.to_string().parse::<u32>().unwrap())
+    } else {
+        (0, 0)
+    };
+
+    let last_digit: (usize, u32) = patterns.iter()
+        .fold(initial_last_result, |result, pattern| {
+            if let Some(found_pattern_index) = line.rfind(pattern.0) {
+                if found_pattern_index + 1 > result.0 {
+                    return (found_pattern_index, pattern.1);
+                }
+            }
+
+            result
+        });
+
+    first_digit.1 * 10 + last_digit.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod examples {
+        include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!("OUT_DIR"), "/fuzz_tests.rs"));
+    }
+
+    mod properties {
+        use super::*;
+        use aoc_common::{check, Source};
+
+        const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+        /// Letters that appear in none of the words, so filler never spells a digit by accident.
+        const FILLER: [char; 8] = ['a', 'b', 'c', 'd', 'j', 'k', 'q', 'z'];
+
+        /// A line as tokens, each a digit, a spelled out digit or a filler letter, along with the
+        /// line as part one sees it once every word is replaced by its digit.
+        fn line(source: &mut Source) -> (String, String) {
+            let mut text = String::new();
+            let mut digits = String::new();
+
+            for _ in 0..source.usize_in(0..=12) {
+                match source.usize_in(0..=2) {
+                    0 => {
+                        let char = *source.choose(&FILLER);
+                        text.push(char);
+                        digits.push(char);
+                    }
+                    1 => {
+                        let digit = source.u32_in(1..=9);
+                        text.push_str(WORDS[digit as usize - 1]);
+                        digits.push(char::from_digit(digit, 10).unwrap());
+                    }
+                    _ => {
+                        let digit = char::from_digit(source.u32_in(0..=9), 10).unwrap();
+                        text.push(digit);
+                        digits.push(digit);
+                    }
+                }
+            }
+
+            (text, digits)
+        }
+
+        fn lines(source: &mut Source) -> Vec<(String, String)> {
+            source.vec_of(0..=20, line)
+        }
+
+        fn join(lines: &[(String, String)], part: fn(&(String, String)) -> &String) -> String {
+            lines.iter().map(part).cloned().collect::<Vec<_>>().join("\n")
+        }
+
+        #[test]
+        fn spelled_digits_read_as_digits() {
+            check("part two reads words as their digits", lines, |lines| {
+                let text = join(lines, |(text, _)| text);
+                let digits = join(lines, |(_, digits)| digits);
+
+                assert_eq!(get_text_calibration_value(&text), get_calibration_value(&digits));
+            });
+        }
+
+        #[test]
+        fn lines_add_up() {
+            check("calibration values are sums over lines", lines, |lines| {
+                let text = join(lines, |(text, _)| text);
+
+                let part_one: u32 = lines.iter().map(|(text, _)| get_calibration_value(text)).sum();
+                let part_two: u32 = lines.iter().map(|(text, _)| get_text_calibration_value(text)).sum();
+
+                assert_eq!(get_calibration_value(&text), part_one);
+                assert_eq!(get_text_calibration_value(&text), part_two);
+                assert!(lines.iter().all(|(text, _)| get_text_calibration_value(text) <= 99));
+            });
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
@@ -0,0 +1,40 @@
+//! What `aoc repl` shows about a record of games.
+
+use aoc_common::{get_item, Explorer};
+
+use crate::{Bag, Day02, Game};
+
+pub const EXPLORER: Explorer<Day02> = Explorer {
+    usage: "query <game>            the rounds, smallest bag and whether the game is possible",
+    inspect: |games| inspect(games),
+    query: |games, arguments| query(games, arguments),
+};
+
+fn inspect(games: &[Game]) -> String {
+    let bag = Bag::new(12, 13, 14);
+    let possible = games.iter().filter(|game| game.check_if_possible(&bag)).count();
+    let rounds: usize = games.iter().map(|game| game.get_rounds().len()).sum();
+
+    format!("{} game(s) of {rounds} round(s), {possible} possible with 12 red, 13 green and 14 blue", games.len())
+}
+
+fn query(games: &[Game], arguments: &[&str]) -> Result<String, String> {
+    let game = get_item(games, arguments, "game")?;
+    let bag = game.get_smallest_required_bag();
+
+    let rounds: Vec<String> = game
+        .get_rounds()
+        .iter()
+        .map(|round| format!("{} red, {} green, {} blue", round.red, round.green, round.blue))
+        .collect();
+
+    Ok(format!(
+        "rounds: {}\nsmallest bag: {} red, {} green, {} blue, power {}\npossible with 12 red, 13 green and 14 blue: {}",
+        rounds.join("; "),
+        bag.red,
+        bag.green,
+        bag.blue,
+        bag.get_power(),
+        game.check_if_possible(&Bag::new(12, 13, 14))
+    ))
+}
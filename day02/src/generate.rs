@@ -0,0 +1,57 @@
+//! Game records for `aoc generate`, each game's possibility and smallest bag tallied while
+//! its rounds are drawn.
+
+use std::fmt::Write;
+
+use aoc_common::{get_answers, get_size, Args, Generated, Generator, Rng};
+
+pub const GENERATOR: Generator = Generator {
+    options: "--games <n> --rounds <n> --max-cubes <n>",
+    generate,
+};
+
+/// Games of up to `--rounds` handfuls, each showing one to three colours in any order.
+fn generate(rng: &mut Rng, args: &mut Args) -> Result<Generated, String> {
+    let games = get_size(args, "--games", 100, 1)?;
+    let rounds = get_size(args, "--rounds", 6, 1)?;
+    let max_cubes = get_size(args, "--max-cubes", 20, 1)?;
+
+    let mut input = String::new();
+    let (mut part_one, mut part_two) = (0, 0);
+
+    for id in 1..=games {
+        let mut required = [0; 3];
+        let mut handfuls = vec![];
+
+        for _ in 0..1 + rng.below(rounds) {
+            let mut colours = vec![0, 1, 2];
+            for index in (1..3).rev() {
+                colours.swap(index, rng.below(index + 1));
+            }
+            colours.truncate(1 + rng.below(3));
+
+            let cubes: Vec<String> = colours
+                .iter()
+                .map(|&colour| {
+                    let count = 1 + rng.below(max_cubes);
+                    required[colour] = required[colour].max(count);
+                    format!("{count} {}", ["red", "green", "blue"][colour])
+                })
+                .collect();
+
+            handfuls.push(cubes.join(", "));
+        }
+
+        if required[0] <= 12 && required[1] <= 13 && required[2] <= 14 {
+            part_one += id as u64;
+        }
+        part_two += required.iter().map(|&count| count as u64).product::<u64>();
+
+        let _ = writeln!(input, "Game {id}: {}", handfuls.join("; "));
+    }
+
+    Ok(Generated {
+        input,
+        answers: get_answers(Some(part_one), Some(part_two)),
+    })
+}
@@ -0,0 +1,339 @@
+//! Day 2: Cube Conundrum
+//!
+//! Games of coloured cubes drawn from a bag, checked against the bag's contents.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::{parse_number, Answer, Explorer, Generator, ParseError, Solution};
+
+mod explore;
+mod generate;
+
+/// How many cubes of each colour a bag holds.
+#[derive(Clone, Debug)]
+pub struct Bag {
+    /// Number of red cubes.
+    pub red: i32,
+    /// Number of green cubes.
+    pub green: i32,
+    /// Number of blue cubes.
+    pub blue: i32,
+}
+
+impl Bag {
+    /// Creates a bag holding the given number of red, green and blue cubes.
+    pub fn new(red: i32, green: i32, blue: i32) -> Self {
+        Bag {
+            red,
+            green,
+            blue,
+        }
+    }
+
+    /// The number of red, green and blue cubes multiplied together, which needs more than 64 bits
+    /// for large counts.
+    pub fn get_power(&self) -> i128 {
+        i128::from(self.red) * i128::from(self.green) * i128::from(self.blue)
+    }
+}
+
+/// The cubes revealed in one handful, with zero for colours not shown.
+#[derive(Debug)]
+pub struct GameRound {
+    /// Number of red cubes.
+    pub red: i32,
+    /// Number of green cubes.
+    pub green: i32,
+    /// Number of blue cubes.
+    pub blue: i32,
+}
+
+impl GameRound {
+    /// Creates a round showing the given number of red, green and blue cubes.
+    pub fn new(red: i32, green: i32, blue: i32) -> Self {
+        GameRound {
+            red,
+            green,
+            blue,
+        }
+    }
+}
+
+/// One line of the input: `Game <id>: <rounds>`.
+#[derive(Debug)]
+pub struct Game {
+    id: u32,
+    rounds: Vec<GameRound>,
+}
+
+impl Game {
+    /// Creates the game numbered `id` from its rounds, in the order they were played.
+    pub fn new(id: u32, rounds: Vec<GameRound>) -> Self {
+        Game {
+            id,
+            rounds,
+        }
+    }
+
+    /// The number after `Game` in the input.
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    /// The rounds of the game, in the order they were played.
+    pub fn get_rounds(&self) -> &[GameRound] {
+        &self.rounds
+    }
+
+    /// The fewest cubes of each colour that make every round of the game possible.
+    pub fn get_smallest_required_bag(&self) -> Bag {
+        let mut bag = Bag::new(0, 0, 0);
+
+        for round in &self.rounds {
+            bag.red = std::cmp::max::<i32>(bag.red, round.red);
+            bag.blue = std::cmp::max::<i32>(bag.blue, round.blue);
+            bag.green = std::cmp::max::<i32>(bag.green, round.green);
+        }
+
+        bag
+    }
+
+    /// Whether every round could have been drawn from `bag`.
+    pub fn check_if_possible(&self, bag: &Bag) -> bool {
+        for round in &self.rounds
+        {
+            if round.red > bag.red {
+                return false;
+            }
+            if round.green > bag.green {
+                return false;
+            }
+            if round.blue > bag.blue {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (game, rounds) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(line, "expected `Game <id>: <rounds>`"))?;
+
+        let id = game
+            .strip_prefix("Game ")
+            .ok_or_else(|| ParseError::new(game, "expected `Game <id>`"))?;
+        let id = parse_number::<u32>(id)?;
+
+        let mut game_rounds = vec![];
+
+        for round in rounds.split(';') {
+            let mut game_round = GameRound::new(0, 0, 0);
+
+            for inner_elem in round.split(',') {
+                let inner_elem = inner_elem.trim();
+                let (count, colour) = inner_elem
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::new(inner_elem, "expected `<count> <colour>`"))?;
+
+                let count = parse_number::<i32>(count)?;
+
+                match colour {
+                    "red" => game_round.red = count,
+                    "green" => game_round.green = count,
+                    "blue" => game_round.blue = count,
+                    _ => return Err(ParseError::new(colour, "unsupported colour, expected red, green or blue")),
+                }
+            }
+
+            game_rounds.push(game_round);
+        }
+
+        Ok(Game::new(id, game_rounds))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::empty_input(input, "expected at least one game"));
+    }
+
+    input.lines().map(str::parse).collect()
+}
+
+/// Part one: sums the ids of the games possible with `bag`.
+pub fn sum_of_ids(bag: &Bag, games: &[Game]) -> u64 {
+    games.iter()
+        .filter(|game| game.check_if_possible(bag))
+        .fold(0, |result, game| result + u64::from(game.id))
+}
+
+/// Part two: sums the power of the smallest bag each game needs.
+pub fn sum_of_powers(games: &[Game]) -> i128 {
+    games.iter()
+        .map(|game| game.get_smallest_required_bag())
+        .fold(0, |sum, bag| sum + bag.get_power())
+}
+
+/// The [`Solution`] for day 2, parsing one [`Game`] per line.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let games = parse_input(input)?;
+        aoc_common::debug!("{} games", games.len());
+
+        Ok(games)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        sum_of_ids(&Bag::new(12, 13, 14), input).into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        sum_of_powers(input).into()
+    }
+
+    fn get_explorer() -> Option<Explorer<Self>> {
+        Some(explore::EXPLORER)
+    }
+
+    fn get_generator() -> Option<Generator> {
+        Some(generate::GENERATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_input() {
+        let text = "Game 1: 3 blue, 4 red\nGame 2: 1 purple";
+
+        let error = Day02::parse(text).err().unwrap().locate(text);
+
+        assert_eq!(error.get_message(), "unsupported colour, expected red, green or blue");
+        assert_eq!(error.get_location().map(|location| (location.line, location.column)), Some((2, 11)));
+        assert!(Day02::parse("Game x: 1 red").is_err());
+        assert!(Day02::parse("Game 1: red").is_err());
+        assert!(Day02::parse("").err().unwrap().is_empty_input());
+    }
+
+    #[test]
+    fn ids_come_from_the_input() {
+        let games = Day02::parse("Game 7: 1 red\nGame 3: 20 blue\nGame 12: 2 green").unwrap();
+
+        assert_eq!(games.iter().map(Game::get_id).collect::<Vec<_>>(), vec![7, 3, 12]);
+        assert_eq!(Day02::part_one(&games), Answer::Unsigned(19));
+    }
+
+    mod examples {
+        include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!("OUT_DIR"), "/fuzz_tests.rs"));
+    }
+
+    mod properties {
+        use super::*;
+        use aoc_common::{check, Source};
+
+        fn game_round(source: &mut Source) -> GameRound {
+            GameRound::new(source.i32_in(0..=20), source.i32_in(0..=20), source.i32_in(0..=20))
+        }
+
+        fn game(source: &mut Source) -> Game {
+            Game::new(source.u32_in(1..=100), source.vec_of(1..=6, game_round))
+        }
+
+        fn bag(source: &mut Source) -> Bag {
+            Bag::new(source.i32_in(0..=20), source.i32_in(0..=20), source.i32_in(0..=20))
+        }
+
+        /// `Game <id>: <rounds>` as the puzzle writes it, colours in a varying order.
+        fn format_game(game: &Game) -> String {
+            let id = game.id as usize;
+            let rounds: Vec<String> = game.rounds.iter()
+                .map(|round| {
+                    let mut cubes: Vec<String> = [(round.blue, "blue"), (round.red, "red"), (round.green, "green")]
+                        .iter()
+                        .filter(|(count, _)| *count > 0)
+                        .map(|(count, colour)| format!("{count} {colour}"))
+                        .collect();
+                    if cubes.is_empty() {
+                        cubes.push("0 red".to_string());
+                    }
+                    let shift = id % cubes.len();
+                    cubes.rotate_left(shift);
+
+                    cubes.join(", ")
+                })
+                .collect();
+
+            format!("Game {id}: {}", rounds.join("; "))
+        }
+
+        #[test]
+        fn smallest_bag_is_smallest() {
+            check("a game fits its smallest bag but no smaller one", game, |game| {
+                let bag = game.get_smallest_required_bag();
+
+                assert!(game.check_if_possible(&bag));
+                assert_eq!(bag.red, game.rounds.iter().map(|round| round.red).max().unwrap());
+                assert_eq!(bag.green, game.rounds.iter().map(|round| round.green).max().unwrap());
+                assert_eq!(bag.blue, game.rounds.iter().map(|round| round.blue).max().unwrap());
+
+                let smaller = [
+                    Bag::new(bag.red - 1, bag.green, bag.blue),
+                    Bag::new(bag.red, bag.green - 1, bag.blue),
+                    Bag::new(bag.red, bag.green, bag.blue - 1),
+                ];
+
+                for smaller in smaller.iter().filter(|smaller| smaller.red.min(smaller.green).min(smaller.blue) >= 0) {
+                    assert!(!game.check_if_possible(smaller), "{smaller:?} is enough too");
+                }
+            });
+        }
+
+        #[test]
+        fn larger_bags_allow_more_games() {
+            check(
+                "sum_of_ids only grows with the bag",
+                |source| (source.vec_of(0..=10, game), bag(source), bag(source)),
+                |(games, bag, extra)| {
+                    let larger = Bag::new(bag.red + extra.red, bag.green + extra.green, bag.blue + extra.blue);
+
+                    assert!(sum_of_ids(bag, games) <= sum_of_ids(&larger, games));
+                    assert!(sum_of_ids(&Bag::new(20, 20, 20), games) == games.iter().map(|game| u64::from(game.id)).sum());
+                },
+            );
+        }
+
+        #[test]
+        fn formatted_games_parse_back() {
+            check("games survive formatting and parsing", |source| source.vec_of(1..=10, game), |games| {
+                let text: Vec<String> = games.iter().map(format_game).collect();
+                let parsed = parse_input(&text.join("\n")).unwrap();
+
+                assert_eq!(sum_of_powers(&parsed), sum_of_powers(games));
+                assert_eq!(sum_of_ids(&Bag::new(12, 13, 14), &parsed), sum_of_ids(&Bag::new(12, 13, 14), games));
+            });
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
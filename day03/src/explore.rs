@@ -0,0 +1,61 @@
+//! What `aoc repl` shows about an engine schematic.
+
+use aoc_common::{get_argument, Explorer};
+
+use crate::{Day03, Engine, Field};
+
+pub const EXPLORER: Explorer<Day03> = Explorer {
+    usage: "query <x> <y>           the field at column x of row y, from 0 at the top left",
+    inspect,
+    query,
+};
+
+fn inspect(engine: &Engine) -> String {
+    let (width, height) = engine.get_size();
+    let mut numbers = 0;
+    let mut symbols = 0;
+    let mut gears = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let follows_digit = x > 0 && matches!(engine.get_field(x - 1, y), Some(Field::Number(..)));
+
+            match engine.get_field(x, y) {
+                Some(Field::Number(..)) if !follows_digit => numbers += 1,
+                Some(Field::Symbol('*')) => gears += 1,
+                Some(Field::Symbol(_)) => symbols += 1,
+                _ => {}
+            }
+        }
+    }
+
+    format!("{width}x{height} schematic, {numbers} number(s), {} symbol(s) of which {gears} `*`", symbols + gears)
+}
+
+fn query(engine: &Engine, arguments: &[&str]) -> Result<String, String> {
+    let x: usize = get_argument(arguments, 0, "x")?;
+    let y: usize = get_argument(arguments, 1, "y")?;
+
+    let describe = |field: &Field| match field {
+        Field::Number(uid, value) => format!("number {value} (uid {uid})"),
+        Field::Symbol(symbol) => format!("symbol {symbol}"),
+        Field::None => "empty".to_string(),
+    };
+
+    let Some(field) = engine.get_field(x, y) else {
+        let (width, height) = engine.get_size();
+        return Err(format!("({x}, {y}) is outside the {width}x{height} schematic"));
+    };
+
+    let neighbours: Vec<String> = engine
+        .get_adjacent_numbers(x, y)
+        .into_iter()
+        .filter(|(uid, _)| !matches!(field, Field::Number(own, _) if own == uid))
+        .map(|(_, value)| value.to_string())
+        .collect();
+
+    match neighbours.is_empty() {
+        true => Ok(format!("({x}, {y}): {}", describe(field))),
+        false => Ok(format!("({x}, {y}): {}, next to {}", describe(field), neighbours.join(", "))),
+    }
+}
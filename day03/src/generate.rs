@@ -0,0 +1,104 @@
+//! Engine schematics for `aoc generate`, summed by walking around each number once rather
+//! than by the solver's scan for symbols.
+
+use std::collections::HashMap;
+
+use aoc_common::{get_answers, get_size, Args, Generated, Generator, Rng};
+
+pub const GENERATOR: Generator = Generator {
+    options: "--width <n> --height <n>",
+    generate,
+};
+
+const SYMBOLS: &[u8] = b"*#+$/@%=&-";
+
+/// A grid of one to three digit numbers and symbols scattered over dots, numbers never touching.
+fn generate(rng: &mut Rng, args: &mut Args) -> Result<Generated, String> {
+    let width = get_size(args, "--width", 140, 1)?;
+    let height = get_size(args, "--height", 140, 1)?;
+
+    let mut grid = vec![vec![b'.'; width]; height];
+
+    for row in &mut grid {
+        let mut x = 0;
+
+        while x < width {
+            match rng.below(20) {
+                0 | 1 => {
+                    let length = (1 + rng.below(3)).min(width - x);
+                    row[x] = b'1' + rng.below(9) as u8;
+                    for cell in &mut row[x + 1..x + length] {
+                        *cell = b'0' + rng.below(10) as u8;
+                    }
+                    // Leave a dot after the number so that the next one cannot run into it.
+                    x += length + 1;
+                }
+                2 => {
+                    row[x] = SYMBOLS[rng.below(SYMBOLS.len())];
+                    x += 1;
+                }
+                _ => x += 1,
+            }
+        }
+    }
+
+    let (part_one, part_two) = get_schematic_sums(&grid);
+
+    let mut input = String::with_capacity((width + 1) * height);
+    for row in &grid {
+        input.push_str(std::str::from_utf8(row).unwrap_or_default());
+        input.push('\n');
+    }
+
+    Ok(Generated {
+        input,
+        answers: get_answers(Some(part_one), Some(part_two)),
+    })
+}
+
+/// Part numbers, and gears, a `*` touching exactly two numbers, found by walking around each
+/// number once.
+fn get_schematic_sums(grid: &[Vec<u8>]) -> (u64, u64) {
+    let is_symbol = |byte: u8| byte != b'.' && !byte.is_ascii_digit();
+    let mut gears: HashMap<(usize, usize), Vec<u64>> = HashMap::new();
+    let mut part_one = 0;
+
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            let mut value = 0;
+            while x < row.len() && row[x].is_ascii_digit() {
+                value = value * 10 + u64::from(row[x] - b'0');
+                x += 1;
+            }
+
+            let mut is_part = false;
+
+            let (top, left) = (y.saturating_sub(1), start.saturating_sub(1));
+
+            for (neighbour_y, neighbour_row) in grid.iter().enumerate().skip(top).take(y + 2 - top) {
+                for (neighbour_x, &byte) in neighbour_row.iter().enumerate().take(x + 1).skip(left) {
+                    is_part |= is_symbol(byte);
+                    if byte == b'*' {
+                        gears.entry((neighbour_x, neighbour_y)).or_default().push(value);
+                    }
+                }
+            }
+
+            if is_part {
+                part_one += value;
+            }
+        }
+    }
+
+    let part_two = gears.values().filter(|values| values.len() == 2).map(|values| values[0] * values[1]).sum();
+
+    (part_one, part_two)
+}
@@ -0,0 +1,354 @@
+//! Day 3: Gear Ratios
+//!
+//! An engine schematic of part numbers and symbols laid out on a grid.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc_common::{Answer, Explorer, Generator, ParseError, Solution};
+
+mod explore;
+mod generate;
+
+/// One character of the schematic.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Field {
+    /// A digit of a number, as the number's uid and its value. Each number has its own uid, so
+    /// two equal numbers can be told apart.
+    Number(u32, u32),
+    /// Anything other than a digit or `.`.
+    Symbol(char),
+    /// A `.`.
+    None,
+}
+
+/// The engine schematic, one field per character of the input.
+pub struct Engine {
+    fields: Vec<Vec<Field>>,
+}
+
+impl FromStr for Engine {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::empty_input(value, "expected an engine schematic"));
+        }
+
+        let mut fields: Vec<Vec<Field>> = vec![];
+        let lines = value.lines();
+
+        let mut uid = 0;
+
+        for line in lines {
+            let mut accumulator: u32 = 0;
+            let mut multiplier = Some(1u32);
+            let mut count = 0;
+
+            let size = line.char_indices().count();
+            let mut index = size;
+
+            if let Some(first_row) = fields.first() {
+                if size != first_row.len() {
+                    return Err(ParseError::new(
+                        line,
+                        format!("expected {} columns like the first row, found {size}", first_row.len()),
+                    ));
+                }
+            }
+
+            let chars = line.chars().rev();
+
+            let mut field_row: Vec<Field> = vec![Field::None; size];
+
+            for char in chars {
+                if char.is_ascii_digit() {
+                    accumulator = multiplier
+                        .and_then(|multiplier| multiplier.checked_mul(char.to_digit(10).unwrap()))
+                        .and_then(|value| value.checked_add(accumulator))
+                        .ok_or_else(|| ParseError::new(line, "number too large"))?;
+                    multiplier = multiplier.and_then(|multiplier| multiplier.checked_mul(10));
+                    count += 1;
+                }
+                else if count > 0 {
+                    for _ in 0..count {
+                        index -= 1;
+
+                        field_row[index] = Field::Number(uid, accumulator);
+                    }
+
+                    uid +=1;
+
+                    accumulator = 0;
+                    multiplier = Some(1);
+                    count = 0;
+
+                    index -= 1;
+
+                    if char == '.' {
+                        field_row[index] = Field::None;
+                    }
+                    else {
+                        field_row[index] = Field::Symbol(char);
+                    }
+                } else {
+                    index -= 1;
+
+                    if char == '.' {
+                        field_row[index] = Field::None;
+                    }
+                    else {
+                        field_row[index] = Field::Symbol(char);
+                    }
+                }
+            }
+
+            if count > 0 {
+                for _ in 0..count {
+                    index -= 1;
+                    field_row[index] = Field::Number(uid, accumulator);
+                }
+
+                uid +=1;
+            }
+
+            fields.push(field_row);
+        }
+
+        if fields.first().is_none_or(|row| row.is_empty()) {
+            return Err(ParseError::new(value.lines().next().unwrap_or(value), "expected the first row of the schematic"));
+        }
+
+        Ok(Engine {
+            fields
+        })
+    }
+}
+
+impl Engine {
+    /// Part one: sums every number adjacent to a symbol, including diagonally, once each.
+    pub fn get_sum(&self) -> u64 {
+        let mut counted = HashSet::new();
+        let mut accumulator = 0;
+
+        for (x, y) in self.get_symbols(|_| true) {
+            for (uid, value) in self.get_adjacent_numbers(x, y) {
+                if counted.insert(uid) {
+                    accumulator += u64::from(value);
+                }
+            }
+        }
+
+        accumulator
+    }
+
+    /// Part two: sums the product of the two numbers around every `*` touching exactly two.
+    pub fn get_gear_ratios_sum(&self) -> u128 {
+        self.get_symbols(|symbol| symbol == '*')
+            .filter_map(|(x, y)| match self.get_adjacent_numbers(x, y)[..] {
+                [(_, first), (_, second)] => Some(u128::from(first) * u128::from(second)),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// The width and height of the schematic.
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.fields.first().map_or(0, Vec::len), self.fields.len())
+    }
+
+    fn get_symbols(&self, is_wanted: impl Fn(char) -> bool + Copy + 'static) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.fields.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(_, field)| matches!(field, Field::Symbol(symbol) if is_wanted(*symbol)))
+                .map(move |(x, _)| (x, y))
+        })
+    }
+
+    /// The distinct numbers around `(x, y)` as their uid and value; a number spanning several
+    /// neighbouring fields is listed once.
+    pub fn get_adjacent_numbers(&self, x: usize, y: usize) -> Vec<(u32, u32)> {
+        let mut numbers: Vec<(u32, u32)> = vec![];
+
+        for neighbour_y in y.saturating_sub(1)..=y + 1 {
+            for neighbour_x in x.saturating_sub(1)..=x + 1 {
+                if let Some(Field::Number(uid, value)) = self.get_field(neighbour_x, neighbour_y) {
+                    if !numbers.iter().any(|(seen, _)| seen == uid) {
+                        numbers.push((*uid, *value));
+                    }
+                }
+            }
+        }
+
+        numbers
+    }
+
+    /// The field at column `x` of row `y`, counting from 0 at the top left.
+    pub fn get_field(&self, x: usize, y: usize) -> Option<&Field> {
+        if let Some(fields) = self.fields.get(y) {
+            if let Some(field) = fields.get(x) {
+                return Some(field)
+            }
+        }
+
+        None
+    }
+}
+
+/// The [`Solution`] for day 3, parsing the input into an [`Engine`].
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    type Input = Engine;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let engine: Engine = input.parse()?;
+        let (width, height) = engine.get_size();
+        aoc_common::debug!("{width}x{height} schematic");
+
+        Ok(engine)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        input.get_sum().into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        input.get_gear_ratios_sum().into()
+    }
+
+    fn get_explorer() -> Option<Explorer<Self>> {
+        Some(explore::EXPLORER)
+    }
+
+    fn get_generator() -> Option<Generator> {
+        Some(generate::GENERATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_or_panic;
+
+    #[test]
+    fn malformed_input() {
+        let text = "467..\n...*\n..35.";
+
+        let error = Day03::parse(text).err().unwrap().locate(text);
+
+        assert_eq!(error.get_message(), "expected 5 columns like the first row, found 4");
+        assert_eq!(error.get_location().map(|location| location.line), Some(2));
+        assert!(Day03::parse("").err().unwrap().is_empty_input());
+        assert!(!Day03::parse("\n467..").err().unwrap().is_empty_input());
+    }
+
+    mod examples {
+        include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!("OUT_DIR"), "/fuzz_tests.rs"));
+    }
+
+    mod properties {
+        use super::*;
+        use aoc_common::{check, Source};
+
+        const CELLS: [char; 18] = ['.', '.', '.', '.', '1', '2', '3', '4', '5', '6', '7', '8', '9', '0', '*', '#', '+', '$'];
+
+        /// A schematic's rows, mostly empty, shrinking towards all dots. Numbers stay within a `u32`.
+        fn schematic(source: &mut Source) -> Vec<String> {
+            let width = source.usize_in(1..=9);
+            let height = source.usize_in(1..=10);
+
+            (0..height).map(|_| (0..width).map(|_| *source.choose(&CELLS)).collect()).collect()
+        }
+
+        fn parse(rows: &[String]) -> Engine {
+            parse_or_panic(&rows.join("\n"))
+        }
+
+        /// Every number as its row, first and last column, and value.
+        fn get_numbers(rows: &[String]) -> Vec<(usize, usize, usize, u64)> {
+            let mut numbers = vec![];
+
+            for (y, row) in rows.iter().enumerate() {
+                let row: Vec<char> = row.chars().collect();
+                let mut x = 0;
+
+                while x < row.len() {
+                    if row[x].is_ascii_digit() {
+                        let start = x;
+                        while x < row.len() && row[x].is_ascii_digit() {
+                            x += 1;
+                        }
+                        let value = row[start..x].iter().collect::<String>().parse().unwrap();
+                        numbers.push((y, start, x - 1, value));
+                    } else {
+                        x += 1;
+                    }
+                }
+            }
+
+            numbers
+        }
+
+        fn is_adjacent((y, start, end, _): (usize, usize, usize, u64), x: usize, symbol_y: usize) -> bool {
+            symbol_y + 1 >= y && symbol_y <= y + 1 && x + 1 >= start && x <= end + 1
+        }
+
+        fn get_symbols(rows: &[String]) -> Vec<(usize, usize)> {
+            rows.iter()
+                .enumerate()
+                .flat_map(|(y, row)| row.chars().enumerate().map(move |(x, char)| (x, y, char)))
+                .filter(|(_, _, char)| *char != '.' && !char.is_ascii_digit())
+                .map(|(x, y, _)| (x, y))
+                .collect()
+        }
+
+        #[test]
+        fn part_numbers_count_once() {
+            check("get_sum counts each number next to a symbol once", schematic, |rows| {
+                let symbols = get_symbols(rows);
+                let expected: u64 = get_numbers(rows)
+                    .into_iter()
+                    .filter(|number| symbols.iter().any(|&(x, y)| is_adjacent(*number, x, y)))
+                    .map(|(_, _, _, value)| value)
+                    .sum();
+
+                assert_eq!(parse(rows).get_sum(), expected);
+            });
+        }
+
+        #[test]
+        fn gears_touch_two_numbers() {
+            check("get_gear_ratios_sum multiplies exactly two numbers around a `*`", schematic, |rows| {
+                let numbers = get_numbers(rows);
+                let expected: u128 = get_symbols(rows)
+                    .into_iter()
+                    .filter(|&(x, y)| rows[y].as_bytes()[x] == b'*')
+                    .map(|(x, y)| {
+                        let values: Vec<u64> = numbers
+                            .iter()
+                            .filter(|number| is_adjacent(**number, x, y))
+                            .map(|(_, _, _, value)| *value)
+                            .collect();
+
+                        if values.len() == 2 { u128::from(values[0]) * u128::from(values[1]) } else { 0 }
+                    })
+                    .sum();
+
+                assert_eq!(parse(rows).get_gear_ratios_sum(), expected);
+            });
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
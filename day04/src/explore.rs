@@ -0,0 +1,40 @@
+//! What `aoc repl` shows about a pile of scratchcards.
+
+use aoc_common::{get_argument, get_item, Answer, Explorer};
+
+use crate::{Day04, ScratchCard};
+
+pub const EXPLORER: Explorer<Day04> = Explorer {
+    usage: "query <card>            the matching numbers, points and copies of one card",
+    inspect,
+    query,
+};
+
+fn inspect(scratch_card: &ScratchCard) -> String {
+    let cards = scratch_card.get_cards();
+    let winning = cards.iter().filter(|card| card.get_matching_cards() > 0).count();
+
+    format!("{} card(s), {winning} with at least one match", cards.len())
+}
+
+fn query(scratch_card: &ScratchCard, arguments: &[&str]) -> Result<String, String> {
+    let card = get_item(scratch_card.get_cards(), arguments, "card")?;
+    let number: usize = get_argument(arguments, 0, "card")?;
+    let copies = Answer::from(scratch_card.get_copies().map(|copies| copies[number - 1]));
+
+    let join = |numbers: &[u32]| numbers.iter().map(u32::to_string).collect::<Vec<_>>().join(" ");
+    let matching: Vec<u32> = card
+        .get_owned_numbers()
+        .iter()
+        .filter(|number| card.get_winning_numbers().contains(number))
+        .copied()
+        .collect();
+
+    Ok(format!(
+        "winning: {}\nowned: {}\nmatching: {}, {} point(s)\ncopies: {copies}",
+        join(card.get_winning_numbers()),
+        join(card.get_owned_numbers()),
+        join(&matching),
+        Answer::from(card.get_points())
+    ))
+}
@@ -0,0 +1,72 @@
+//! Scratchcard piles for `aoc generate`, each card dealt with a chosen number of matches, so
+//! that points and copies follow without comparing numbers.
+
+use std::fmt::Write;
+
+use aoc_common::{get_answers, get_size, Args, Generated, Generator, Rng};
+
+pub const GENERATOR: Generator = Generator {
+    options: "--cards <n> --winning <n> --numbers <n> --match-rate <percent>",
+    generate,
+};
+
+/// Cards with distinct numbers from 1 to 99, `--match-rate` percent of them winning anything.
+fn generate(rng: &mut Rng, args: &mut Args) -> Result<Generated, String> {
+    let cards = get_size(args, "--cards", 200, 1)?;
+    let winning = get_size(args, "--winning", 10, 1)?;
+    let numbers = get_size(args, "--numbers", 25, 1)?;
+    let match_rate = get_size(args, "--match-rate", 10, 0)?;
+
+    if winning + numbers > 99 {
+        return Err("--winning and --numbers add up to more than the 99 distinct numbers".to_string());
+    }
+
+    let width = cards.to_string().len();
+    let mut input = String::new();
+    let mut part_one: u64 = 0;
+    let mut copies = vec![1u64; cards];
+    let mut part_two = Some(0u64);
+
+    for index in 0..cards {
+        let matches = if rng.below(100) < match_rate { 1 + rng.below(winning.min(numbers)) } else { 0 };
+
+        // A partial shuffle: winning numbers first, then the numbers you have that do not match.
+        let mut pool: Vec<u32> = (1..=99).collect();
+        for slot in 0..winning + numbers - matches {
+            let other = slot + rng.below(pool.len() - slot);
+            pool.swap(slot, other);
+        }
+
+        let winning_numbers = &pool[..winning];
+        let mut owned_numbers: Vec<u32> = pool[..matches].to_vec();
+        owned_numbers.extend_from_slice(&pool[winning..winning + numbers - matches]);
+        for slot in (1..owned_numbers.len()).rev() {
+            owned_numbers.swap(slot, rng.below(slot + 1));
+        }
+
+        let format = |numbers: &[u32]| numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ");
+        let _ = writeln!(
+            input,
+            "Card {:>width$}: {} | {}",
+            index + 1,
+            format(winning_numbers),
+            format(&owned_numbers)
+        );
+
+        if matches > 0 {
+            part_one += 1 << (matches - 1);
+        }
+
+        let card_copies = copies[index];
+        for won in copies.iter_mut().skip(index + 1).take(matches) {
+            *won = won.saturating_add(card_copies);
+        }
+        // Copies saturate, and a saturated count no longer adds up.
+        part_two = part_two.and_then(|total: u64| total.checked_add(card_copies));
+    }
+
+    Ok(Generated {
+        input,
+        answers: get_answers(Some(part_one), part_two),
+    })
+}
@@ -0,0 +1,291 @@
+//! Day 4: Scratchcards
+//!
+//! Scratchcards scored by how many of their numbers are winning numbers.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::{parse_number, Answer, Explorer, Generator, ParseError, Solution};
+
+mod explore;
+mod generate;
+
+/// One line of the input: `Card <id>: <winning numbers> | <numbers you have>`.
+#[derive(Debug)]
+pub struct Card {
+    winning_numbers: Vec<u32>,
+    owned_numbers: Vec<u32>,
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (card, numbers) = value
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(value, "expected `Card <id>: <winning numbers> | <numbers you have>`"))?;
+
+        let id = card
+            .strip_prefix("Card")
+            .ok_or_else(|| ParseError::new(card, "expected `Card <id>`"))?;
+        parse_number::<u32>(id.trim_start())?;
+
+        let (winnings_text, owned_text) = numbers
+            .split_once('|')
+            .ok_or_else(|| ParseError::after(numbers, "expected `|` between the winning numbers and the numbers you have"))?;
+
+        let winning_numbers = winnings_text
+            .split_whitespace()
+            .map(parse_number::<u32>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let owned_numbers = owned_text
+            .split_whitespace()
+            .map(parse_number::<u32>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Card {
+            winning_numbers,
+            owned_numbers,
+        })
+    }
+}
+
+impl Card {
+    /// The numbers left of the `|`.
+    pub fn get_winning_numbers(&self) -> &[u32] {
+        &self.winning_numbers
+    }
+
+    /// The numbers right of the `|`.
+    pub fn get_owned_numbers(&self) -> &[u32] {
+        &self.owned_numbers
+    }
+
+    /// 1 point for the first matching number, doubled for every further match, or `None` past
+    /// 63 matches, when the points no longer fit in a `u64`.
+    pub fn get_points(&self) -> Option<u64> {
+        match self.get_matching_cards() {
+            0 => Some(0),
+            matches => u32::try_from(matches - 1).ok().and_then(|shift| 1_u64.checked_shl(shift)),
+        }
+    }
+
+    /// How many of the numbers you have are winning numbers.
+    pub fn get_matching_cards(&self) -> usize {
+        self.owned_numbers
+            .iter()
+            .filter(|owned_number| self.winning_numbers.contains(owned_number))
+            .count()
+    }
+}
+
+/// Every card in the pile, in input order.
+pub struct ScratchCard {
+    cards: Vec<Card>,
+}
+
+impl FromStr for ScratchCard {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::empty_input(value, "expected at least one card"));
+        }
+
+        let cards = value.lines().map(str::parse).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ScratchCard {
+            cards,
+        })
+    }
+}
+
+impl ScratchCard {
+    /// The cards, in input order.
+    pub fn get_cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Part one: the points of all cards added together, or `None` if they overflow a `u64`.
+    pub fn get_total_points(&self) -> Option<u64> {
+        self.cards.iter().try_fold(0_u64, |total, card| total.checked_add(card.get_points()?))
+    }
+
+    /// Part two: how many cards you end up with when each match wins a copy of the following cards,
+    /// or `None` if they overflow a `u64`.
+    pub fn get_won_cards(&self) -> Option<u64> {
+        self.get_copies()?.iter().try_fold(0_u64, |total, copies| total.checked_add(*copies))
+    }
+
+    /// How many copies of each card you end up with, the original included, or `None` once a
+    /// count overflows a `u64`, which a chain of about 64 cards each winning the rest can do.
+    pub fn get_copies(&self) -> Option<Vec<u64>> {
+        let cards_count = self.cards.len();
+        let mut copies = vec![1_u64; cards_count];
+
+        for index in 0..cards_count {
+            let card_copies = copies[index];
+            let card = &self.cards[index];
+
+            let points = card.get_matching_cards();
+
+            for offset in 0..points {
+                if let Some(copy) = copies.get_mut(index + offset + 1) {
+                    *copy = (*copy).checked_add(card_copies)?;
+                }
+            }
+        }
+
+        Some(copies)
+    }
+}
+
+/// The [`Solution`] for day 4, parsing the input into a [`ScratchCard`] pile.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    type Input = ScratchCard;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let scratch_card: ScratchCard = input.parse()?;
+        aoc_common::debug!("{} cards", scratch_card.cards.len());
+
+        Ok(scratch_card)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        input.get_total_points().into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        input.get_won_cards().into()
+    }
+
+    fn get_explorer() -> Option<Explorer<Self>> {
+        Some(explore::EXPLORER)
+    }
+
+    fn get_generator() -> Option<Generator> {
+        Some(generate::GENERATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_or_panic;
+
+    #[test]
+    fn malformed_input() {
+        let text = "Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30";
+
+        let error = Day04::parse(text).err().unwrap().locate(text);
+
+        assert_eq!(error.get_message(), "invalid number");
+        assert_eq!(error.get_location().map(|location| (location.line, location.column)), Some((2, 12)));
+        assert!(!Day04::parse("Card 1: 41 48 83 86").err().unwrap().is_empty_input());
+        assert!(Day04::parse("\n").err().unwrap().is_empty_input());
+    }
+
+    /// Card `id` of `count` wins every card after it, so each card has twice the copies of the
+    /// one before it.
+    fn get_chain(count: usize) -> String {
+        (1..=count)
+            .map(|id| {
+                let numbers: Vec<String> = (1..=count - id).map(|number| number.to_string()).collect();
+                format!("Card {id}: {} | {}", numbers.join(" "), numbers.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn large_piles() {
+        // 40 matches make 2 to the 39th points on the first card.
+        let forty = parse_or_panic::<ScratchCard>(&get_chain(41));
+        assert_eq!(forty.get_cards()[0].get_points(), Some(1 << 39));
+        assert_eq!(Day04::part_one(&forty), Answer::Unsigned((1 << 40) - 1));
+
+        // The copies double along the chain: 2 to the 60th, less one, cards in all.
+        let sixty = parse_or_panic::<ScratchCard>(&get_chain(60));
+        assert_eq!(sixty.get_copies().unwrap()[59], 1 << 59);
+        assert_eq!(Day04::part_two(&sixty), Answer::Unsigned((1 << 60) - 1));
+
+        let seventy = parse_or_panic::<ScratchCard>(&get_chain(70));
+        assert_eq!(seventy.get_cards()[5].get_points(), Some(1 << 63));
+        assert_eq!(seventy.get_cards()[4].get_points(), None);
+        assert_eq!(Day04::part_one(&seventy), Answer::Unsolved(aoc_common::OVERFLOW_REASON.to_string()));
+        assert_eq!(Day04::part_two(&seventy), Answer::Unsolved(aoc_common::OVERFLOW_REASON.to_string()));
+    }
+
+    mod examples {
+        include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!("OUT_DIR"), "/fuzz_tests.rs"));
+    }
+
+    mod properties {
+        use super::*;
+        use aoc_common::{check, Source};
+
+        /// Numbers from a small range, so that some of them match.
+        fn card(source: &mut Source) -> Card {
+            Card {
+                winning_numbers: source.vec_of(0..=5, |source| source.u32_in(1..=20)),
+                owned_numbers: source.vec_of(0..=8, |source| source.u32_in(1..=20)),
+            }
+        }
+
+        fn cards(source: &mut Source) -> Vec<Card> {
+            source.vec_of(1..=10, card)
+        }
+
+        /// `Card <id>: <winning numbers> | <numbers you have>`, padded like the puzzle's.
+        fn format_card(id: usize, card: &Card) -> String {
+            let numbers = |numbers: &[u32]| numbers.iter().map(|number| format!("{number:>2}")).collect::<Vec<_>>().join(" ");
+
+            format!("Card {id:>3}: {} | {}", numbers(&card.winning_numbers), numbers(&card.owned_numbers))
+        }
+
+        /// Part two played out literally: each card wins one copy of each of the next cards.
+        fn count_cards(cards: &[Card], index: usize) -> u64 {
+            let won = (index + 1..cards.len()).take(cards[index].get_matching_cards());
+
+            1 + won.map(|won| count_cards(cards, won)).sum::<u64>()
+        }
+
+        #[test]
+        fn points_double_per_match() {
+            check("get_points is 0 or 2 to the matches minus one", card, |card| {
+                let expected = match card.get_matching_cards() {
+                    0 => 0,
+                    matches => 1_u64 << (matches - 1),
+                };
+
+                assert_eq!(card.get_points(), Some(expected));
+            });
+        }
+
+        #[test]
+        fn copies_match_playing_it_out() {
+            check("get_won_cards counts every copy won", cards, |cards| {
+                let expected: u64 = (0..cards.len()).map(|index| count_cards(cards, index)).sum();
+                let text: Vec<String> = cards.iter().enumerate().map(|(index, card)| format_card(index + 1, card)).collect();
+                let scratch_card: ScratchCard = parse_or_panic(&text.join("\n"));
+
+                assert_eq!(scratch_card.get_won_cards(), Some(expected));
+                assert!(expected >= cards.len() as u64);
+                assert_eq!(scratch_card.get_total_points(), cards.iter().map(Card::get_points).sum::<Option<u64>>());
+            });
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
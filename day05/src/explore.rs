@@ -0,0 +1,41 @@
+//! What `aoc repl` shows about an almanac.
+
+use aoc_common::{get_argument, Explorer};
+
+use crate::{Almanac, Day05};
+
+pub const EXPLORER: Explorer<Day05> = Explorer {
+    usage: "query <seed>            where the seed goes at each stage",
+    inspect,
+    query,
+};
+
+fn inspect(almanac: &Almanac) -> String {
+    let seeds: Vec<String> = almanac.get_seeds().iter().map(u64::to_string).collect();
+    let maps: Vec<String> = almanac
+        .get_maps()
+        .iter()
+        .map(|map| format!("{}: {} range(s)", map.get_name(), map.get_ranges().len()))
+        .collect();
+
+    format!("{} seed(s): {}\n{}", seeds.len(), seeds.join(" "), maps.join("\n"))
+}
+
+fn query(almanac: &Almanac, arguments: &[&str]) -> Result<String, String> {
+    let seed: u64 = get_argument(arguments, 0, "seed")?;
+    let mut value = seed;
+    let mut stages = vec![format!("seed {seed}")];
+
+    for map in almanac.get_maps() {
+        let category = map.get_name().split("-to-").nth(1).unwrap_or(map.get_name());
+        let range = map.get_range(value);
+        value = map.get_destination(value);
+
+        stages.push(match range {
+            Some(range) => format!("{category} {value} (by {range})"),
+            None => format!("{category} {value} (unmapped)"),
+        });
+    }
+
+    Ok(stages.join("\n"))
+}
@@ -0,0 +1,154 @@
+//! Almanacs for `aoc generate`, whose seed ranges are sent through the maps as intervals
+//! built alongside them.
+
+use std::fmt::Write;
+
+use aoc_common::{get_answers, get_between, get_size, Args, Generated, Generator, Rng};
+
+pub const GENERATOR: Generator = Generator {
+    options: "--seed-ranges <n> --ranges <n> --max-value <n> --max-seed-range <n>",
+    generate,
+};
+
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// One line of a map: destination, source and length.
+type MapRange = (u64, u64, u64);
+
+/// Seven maps, each cutting `0..max-value` into `--ranges` pieces that it moves around, with
+/// about a quarter of them left unmapped.
+fn generate(rng: &mut Rng, args: &mut Args) -> Result<Generated, String> {
+    let seed_ranges = get_size(args, "--seed-ranges", 10, 1)?;
+    let ranges = get_size(args, "--ranges", 30, 1)?;
+    let max_value = get_size(args, "--max-value", 1 << 32, 2)?;
+    let max_seed_range = get_size(args, "--max-seed-range", 1_000_000, 1)?;
+
+    if max_value > 1 << 62 {
+        return Err("--max-value must be at most 2^62".to_string());
+    }
+
+    let seeds: Vec<(u64, u64)> = (0..seed_ranges)
+        .map(|_| {
+            let start = get_between(rng, 0, max_value - 2);
+            (start, get_between(rng, 1, max_seed_range.min(max_value - start)))
+        })
+        .collect();
+
+    let maps: Vec<Vec<MapRange>> = MAP_NAMES.iter().map(|_| generate_map(rng, ranges, max_value)).collect();
+
+    let mut input = String::from("seeds:");
+    for (start, length) in &seeds {
+        let _ = write!(input, " {start} {length}");
+    }
+    input.push('\n');
+
+    for (name, map) in MAP_NAMES.iter().zip(&maps) {
+        let _ = write!(input, "\n{name} map:\n");
+        for (destination, source, length) in map {
+            let _ = writeln!(input, "{destination} {source} {length}");
+        }
+    }
+
+    // Part one reads every number of the seeds line as a seed, lengths included.
+    let part_one = seeds
+        .iter()
+        .flat_map(|&(start, length)| [start, length])
+        .map(|seed| maps.iter().fold(seed, |value, map| map_value(map, value)))
+        .min();
+
+    let mut intervals: Vec<(u64, u64)> = seeds.iter().map(|&(start, length)| (start, start + length)).collect();
+    for map in &maps {
+        intervals = map_intervals(map, &intervals);
+    }
+    let part_two = intervals.iter().map(|(start, _)| *start).min();
+
+    Ok(Generated {
+        input,
+        answers: get_answers(part_one, part_two),
+    })
+}
+
+fn generate_map(rng: &mut Rng, ranges: usize, max_value: u64) -> Vec<MapRange> {
+    let mut cuts: Vec<u64> = (1..ranges).map(|_| get_between(rng, 1, max_value - 1)).collect();
+    cuts.extend([0, max_value]);
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let pieces: Vec<(u64, u64)> = cuts.windows(2).map(|cut| (cut[0], cut[1] - cut[0])).collect();
+
+    // Destinations are the same pieces laid out in another order, so that none overlap.
+    let mut order: Vec<usize> = (0..pieces.len()).collect();
+    for index in (1..order.len()).rev() {
+        order.swap(index, rng.below(index + 1));
+    }
+
+    let mut destinations = vec![0; pieces.len()];
+    let mut start = 0;
+    for &index in &order {
+        destinations[index] = start;
+        start += pieces[index].1;
+    }
+
+    let mut map: Vec<MapRange> = pieces
+        .iter()
+        .zip(destinations)
+        .filter(|_| rng.below(4) != 0)
+        .map(|(&(source, length), destination)| (destination, source, length))
+        .collect();
+
+    // Maps list their ranges in no particular order.
+    for index in (1..map.len()).rev() {
+        map.swap(index, rng.below(index + 1));
+    }
+
+    map
+}
+
+fn map_value(map: &[MapRange], value: u64) -> u64 {
+    map.iter()
+        .find(|(_, source, length)| (*source..source + length).contains(&value))
+        .map_or(value, |(destination, source, _)| destination + (value - source))
+}
+
+/// Sends the half-open `intervals` through `map`, splitting them where its ranges start and end.
+fn map_intervals(map: &[MapRange], intervals: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut sorted = map.to_vec();
+    sorted.sort_unstable_by_key(|(_, source, _)| *source);
+
+    let mut mapped = vec![];
+
+    for &(start, end) in intervals {
+        let mut cursor = start;
+
+        for &(destination, source, length) in &sorted {
+            if cursor >= end {
+                break;
+            }
+            if source + length <= cursor || source >= end {
+                continue;
+            }
+            if cursor < source {
+                mapped.push((cursor, source));
+                cursor = source;
+            }
+
+            let overlap_end = end.min(source + length);
+            mapped.push((destination + (cursor - source), destination + (overlap_end - source)));
+            cursor = overlap_end;
+        }
+
+        if cursor < end {
+            mapped.push((cursor, end));
+        }
+    }
+
+    mapped
+}
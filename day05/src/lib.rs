@@ -0,0 +1,539 @@
+//! Day 5: If You Give A Seed A Fertilizer
+//!
+//! An almanac of maps that lead each seed through soil, fertilizer and so on to a location.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{parse_number, Answer, Explorer, Generator, ParseError, Solution};
+
+mod explore;
+mod generate;
+
+/// One `<destination> <source> <length>` line of a map.
+#[derive(Debug)]
+pub struct AlmanacRange {
+    destination: u64,
+    source: u64,
+    range: u64,
+}
+
+impl AlmanacRange {
+    /// Where `source` is sent, or `None` when it falls outside of this range.
+    pub fn get_destination(&self, source: u64) -> Option<u64> {
+        // Parsing checks that both ends fit in a u64, so only the offset needs comparing.
+        let offset = source.checked_sub(self.source)?;
+        if offset < self.range {
+            return Some(self.destination + offset);
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for AlmanacRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.destination, self.source, self.range)
+    }
+}
+
+/// A named map such as `seed-to-soil`, made of ranges.
+#[derive(Debug)]
+pub struct AlmanacMap {
+    name: String,
+    ranges: Vec<AlmanacRange>,
+}
+
+impl FromStr for AlmanacMap {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut almanac_ranges: Vec<AlmanacRange> = vec![];
+
+        let (description, ranges) = value.split_once('\n').unwrap_or((value, ""));
+
+        let name = description
+            .trim_end()
+            .strip_suffix(" map:")
+            .filter(|name| name.contains("-to-"))
+            .ok_or_else(|| ParseError::new(description, "expected `<source>-to-<destination> map:`"))?;
+
+        for line in ranges.lines() {
+            let values: Vec<&str> = line.split_whitespace().collect();
+
+            let [destination, source, range] = values[..] else {
+                return Err(ParseError::new(line, "expected `<destination> <source> <length>`"));
+            };
+
+            let almanac_range = AlmanacRange {
+                destination: parse_number(destination)?,
+                source: parse_number(source)?,
+                range: parse_number(range)?,
+            };
+
+            if almanac_range.source.checked_add(almanac_range.range).is_none()
+                || almanac_range.destination.checked_add(almanac_range.range).is_none()
+            {
+                return Err(ParseError::new(line, format!("range runs past {}", u64::MAX)));
+            }
+
+            almanac_ranges.push(almanac_range);
+        }
+
+        Ok(AlmanacMap {
+            name: name.to_string(),
+            ranges: almanac_ranges,
+        })
+    }
+}
+
+impl AlmanacMap {
+    /// The name from the map's heading, such as `seed-to-soil`.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The ranges, in input order.
+    pub fn get_ranges(&self) -> &[AlmanacRange] {
+        &self.ranges
+    }
+
+    /// The first range covering `source`, the one [`AlmanacMap::get_destination`] follows.
+    pub fn get_range(&self, source: u64) -> Option<&AlmanacRange> {
+        self.ranges.iter().find(|range| range.get_destination(source).is_some())
+    }
+
+    /// Where `source` is sent, which is `source` itself when no range covers it.
+    pub fn get_destination(&self, source: u64) -> u64 {
+        for range in &self.ranges {
+            if let Some(destination) = range.get_destination(source) {
+                return destination;
+            }
+        }
+
+        source
+    }
+
+    /// Where every number of `sources`, given as `(start, end)` with `end` exclusive, is sent,
+    /// as ranges in no particular order. Each range is split where the map's ranges begin and
+    /// end, the first range covering a piece shifts it and uncovered pieces stay where they are.
+    pub fn get_destination_ranges(&self, sources: &[(u64, u64)]) -> Vec<(u64, u64)> {
+        let mut pending = sources.to_vec();
+        let mut destinations = vec![];
+
+        for range in &self.ranges {
+            let (range_start, range_end) = (range.source, range.source + range.range);
+            let mut uncovered = vec![];
+
+            for (start, end) in pending {
+                let (covered_start, covered_end) = (start.max(range_start), end.min(range_end));
+
+                if covered_start >= covered_end {
+                    uncovered.push((start, end));
+                    continue;
+                }
+
+                destinations.push((
+                    range.destination + (covered_start - range.source),
+                    range.destination + (covered_end - range.source),
+                ));
+
+                if start < covered_start {
+                    uncovered.push((start, covered_start));
+                }
+                if covered_end < end {
+                    uncovered.push((covered_end, end));
+                }
+            }
+
+            pending = uncovered;
+        }
+
+        destinations.extend(pending);
+        destinations
+    }
+}
+
+/// The seeds and the seven maps between a seed and its location.
+pub struct Almanac {
+    seeds: Vec<u64>,
+    seed_to_soil: AlmanacMap,
+    soil_to_fertilizer: AlmanacMap,
+    fertilizer_to_water: AlmanacMap,
+    water_to_light: AlmanacMap,
+    light_to_temperature: AlmanacMap,
+    temperature_to_humidity: AlmanacMap,
+    humidity_to_location: AlmanacMap,
+}
+
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::empty_input(value, "expected an almanac starting with `seeds: <numbers>`"));
+        }
+
+        let mut split = value.split("\n\n");
+        let mut last = value;
+
+        let seeds_text = split.next().unwrap_or(value);
+        let seeds_numbers = seeds_text
+            .trim_end()
+            .strip_prefix("seeds:")
+            .ok_or_else(|| ParseError::new(seeds_text.lines().next().unwrap_or(""), "expected `seeds: <numbers>`"))?;
+        let seeds = seeds_numbers
+            .split_whitespace()
+            .map(parse_number::<u64>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if seeds.is_empty() {
+            return Err(ParseError::after(seeds_numbers, "expected at least one seed"));
+        }
+
+        let mut next_map = |name: &str| match split.next() {
+            Some(text) => {
+                last = text;
+
+                let map: AlmanacMap = text.parse()?;
+                if map.name != name {
+                    return Err(ParseError::new(text.lines().next().unwrap_or(text), format!("expected `{name} map:`")));
+                }
+
+                Ok(map)
+            }
+            None => Err(ParseError::after(last.trim_end(), format!("expected the {name} map"))),
+        };
+
+        let seed_to_soil = next_map("seed-to-soil")?;
+        let soil_to_fertilizer = next_map("soil-to-fertilizer")?;
+        let fertilizer_to_water = next_map("fertilizer-to-water")?;
+        let water_to_light = next_map("water-to-light")?;
+        let light_to_temperature = next_map("light-to-temperature")?;
+        let temperature_to_humidity = next_map("temperature-to-humidity")?;
+        let humidity_to_location = next_map("humidity-to-location")?;
+
+        if let Some(extra) = split.find(|text| !text.trim().is_empty()) {
+            return Err(ParseError::new(extra.lines().next().unwrap_or(extra), "unexpected text after the last map"));
+        }
+
+        Ok(Almanac {
+            seeds,
+            seed_to_soil,
+            soil_to_fertilizer,
+            fertilizer_to_water,
+            water_to_light,
+            light_to_temperature,
+            temperature_to_humidity,
+            humidity_to_location,
+        })
+    }
+}
+
+impl Almanac {
+    /// The numbers on the `seeds:` line.
+    pub fn get_seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// The seven maps, from `seed-to-soil` to `humidity-to-location`.
+    pub fn get_maps(&self) -> [&AlmanacMap; 7] {
+        [
+            &self.seed_to_soil,
+            &self.soil_to_fertilizer,
+            &self.fertilizer_to_water,
+            &self.water_to_light,
+            &self.light_to_temperature,
+            &self.temperature_to_humidity,
+            &self.humidity_to_location,
+        ]
+    }
+
+    /// Follows `seed` through every map to its location.
+    pub fn get_seed_location_number(&self, seed: u64) -> u64 {
+        let soil = self.seed_to_soil.get_destination(seed);
+        let fertilizer = self.soil_to_fertilizer.get_destination(soil);
+        let water = self.fertilizer_to_water.get_destination(fertilizer);
+        let light = self.water_to_light.get_destination(water);
+        let temperature = self.light_to_temperature.get_destination(light);
+        let humidity = self.temperature_to_humidity.get_destination(temperature);
+
+        self.humidity_to_location.get_destination(humidity)
+    }
+
+    /// Part one: the lowest location of any listed seed.
+    pub fn get_lowest_location_number(&self) -> u64 {
+        self.seeds
+            .iter()
+            .map(|seed| self.get_seed_location_number(*seed))
+            .min().unwrap()
+    }
+
+    /// The seeds line read as part two reads it, pairs of range start and length, or why it
+    /// cannot be: an odd number of seeds, an empty range or one running past `u64::MAX`.
+    pub fn get_seed_ranges(&self) -> Result<Vec<(u64, u64)>, String> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err("expected a length after the last seed range start".to_string());
+        }
+
+        self.seeds.chunks_exact(2)
+            .enumerate()
+            .map(|(index, pair)| match pair[1] {
+                0 => Err(format!("seed range {} is empty", index + 1)),
+                _ if pair[0].checked_add(pair[1]).is_none() => Err(format!("seed range {} runs past {}", index + 1, u64::MAX)),
+                _ => Ok((pair[0], pair[1])),
+            })
+            .collect()
+    }
+
+    /// Part two: the lowest location when the seeds line holds pairs of range start and length,
+    /// or why the seeds line does not hold such pairs.
+    ///
+    /// Each range is sent through the maps whole, so its seeds are never counted one by one.
+    pub fn get_lowest_location_number_from_ranges(&self) -> Result<u64, String> {
+        let ranges = self.get_seed_ranges()?;
+        let count = ranges.len();
+
+        Ok(ranges.into_iter()
+            .enumerate()
+            .map(|(index, (initial_seed, range))| {
+                aoc_common::debug!("seed range {}/{count}: {range} seeds from {initial_seed}", index + 1);
+
+                let lowest = self.get_maps()
+                    .iter()
+                    .fold(vec![(initial_seed, initial_seed + range)], |ranges, map| map.get_destination_ranges(&ranges))
+                    .iter()
+                    .map(|(start, _)| *start)
+                    .min()
+                    .expect("seed ranges are never empty");
+
+                aoc_common::debug!("seed range {}/{count}: lowest location {lowest}", index + 1);
+                lowest
+            })
+            .min()
+            .expect("almanacs hold at least one seed range"))
+    }
+}
+
+/// The [`Solution`] for day 5, parsing the input into an [`Almanac`].
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let almanac: Almanac = input.parse()?;
+        aoc_common::debug!("{} seeds", almanac.seeds.len());
+
+        Ok(almanac)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        input.get_lowest_location_number().into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        input.get_lowest_location_number_from_ranges().map_or_else(Answer::Unsolved, Answer::from)
+    }
+
+    fn get_explorer() -> Option<Explorer<Self>> {
+        Some(explore::EXPLORER)
+    }
+
+    fn get_generator() -> Option<Generator> {
+        Some(generate::GENERATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_or_panic;
+
+    #[test]
+    fn malformed_input() {
+        let text = "seeds: 79 14\n\nseed-to-soil map:\n50 98\n";
+
+        let error = Day05::parse(text).err().unwrap().locate(text);
+
+        assert_eq!(error.get_message(), "expected `<destination> <source> <length>`");
+        assert_eq!(error.get_location().map(|location| location.line), Some(4));
+
+        let text = "seeds: 79 14\n\nsoil-to-seed map:\n50 98 2\n";
+        assert_eq!(Day05::parse(text).err().unwrap().get_message(), "expected `seed-to-soil map:`");
+
+        let text = "seeds: 18446744073709551614 1\n\nseed-to-soil map:\n0 18446744073709551610 10\n";
+        let error = Day05::parse(text).err().unwrap().locate(text);
+        assert_eq!(error.get_message(), "range runs past 18446744073709551615");
+        assert_eq!(error.get_location().map(|location| location.line), Some(4));
+
+        assert!(Day05::parse(" \n").err().unwrap().is_empty_input());
+        assert!(!Day05::parse("soil: 79").err().unwrap().is_empty_input());
+    }
+
+    #[test]
+    fn seeds_that_are_not_ranges() {
+        let maps = "seed-to-soil map:\n50 98 2\n\nsoil-to-fertilizer map:\n0 15 37\n\nfertilizer-to-water map:\n49 53 8\n\n\
+                    water-to-light map:\n88 18 7\n\nlight-to-temperature map:\n45 77 23\n\n\
+                    temperature-to-humidity map:\n0 69 1\n\nhumidity-to-location map:\n60 56 37\n";
+
+        // Part one reads any seeds line; only part two needs pairs of start and length.
+        for (seeds, reason) in [
+            ("79 14 55", "expected a length after the last seed range start"),
+            ("79 14 55 0", "seed range 2 is empty"),
+            ("18446744073709551614 2", "seed range 1 runs past 18446744073709551615"),
+        ] {
+            let almanac = Day05::parse(&format!("seeds: {seeds}\n\n{maps}")).unwrap();
+
+            assert!(matches!(Day05::part_one(&almanac), Answer::Unsigned(_)), "{seeds}");
+            assert_eq!(Day05::part_two(&almanac), Answer::Unsolved(reason.to_string()), "{seeds}");
+        }
+    }
+
+    mod examples {
+        include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!("OUT_DIR"), "/fuzz_tests.rs"));
+    }
+
+    mod properties {
+        use super::*;
+        use aoc_common::{check, Source};
+        use std::collections::BTreeSet;
+
+        const MAP_NAMES: [&str; 7] = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+
+        fn almanac_range(source: &mut Source) -> AlmanacRange {
+            AlmanacRange {
+                destination: source.u64_in(0..=100),
+                source: source.u64_in(0..=100),
+                range: source.u64_in(0..=20),
+            }
+        }
+
+        /// A map whose source ranges, and separately whose destination ranges, never overlap,
+        /// as the puzzle's do: the same lengths laid out twice, in two orders, with gaps.
+        fn almanac_map(source: &mut Source, name: &str) -> AlmanacMap {
+            let lengths = source.vec_of(0..=6, |source| source.u64_in(1..=20));
+
+            let mut order: Vec<usize> = (0..lengths.len()).collect();
+            for index in (1..order.len()).rev() {
+                order.swap(index, source.usize_in(0..=index));
+            }
+
+            let mut sources = vec![0; lengths.len()];
+            let mut start = source.u64_in(0..=20);
+            for (index, length) in lengths.iter().enumerate() {
+                sources[index] = start;
+                start += length + source.u64_in(0..=10);
+            }
+
+            let mut destinations = vec![0; lengths.len()];
+            let mut start = source.u64_in(0..=20);
+            for &index in &order {
+                destinations[index] = start;
+                start += lengths[index] + source.u64_in(0..=10);
+            }
+
+            AlmanacMap {
+                name: name.to_string(),
+                ranges: (0..lengths.len())
+                    .map(|index| AlmanacRange {
+                        destination: destinations[index],
+                        source: sources[index],
+                        range: lengths[index],
+                    })
+                    .collect(),
+            }
+        }
+
+        fn format_map(map: &AlmanacMap) -> String {
+            let mut text = format!("{} map:", map.name);
+
+            for range in &map.ranges {
+                text.push_str(&format!("\n{} {} {}", range.destination, range.source, range.range));
+            }
+
+            text
+        }
+
+        /// An almanac as seed ranges and maps, to be written out as text.
+        fn almanac(source: &mut Source) -> (Vec<(u64, u64)>, Vec<AlmanacMap>) {
+            let seeds = source.vec_of(1..=4, |source| (source.u64_in(0..=150), source.u64_in(1..=20)));
+            let maps = MAP_NAMES.iter().map(|name| almanac_map(source, name)).collect();
+
+            (seeds, maps)
+        }
+
+        fn parse_almanac(seeds: &[u64], maps: &[AlmanacMap]) -> Almanac {
+            let seeds: Vec<String> = seeds.iter().map(u64::to_string).collect();
+            let maps: Vec<String> = maps.iter().map(format_map).collect();
+
+            parse_or_panic(&format!("seeds: {}\n\n{}\n", seeds.join(" "), maps.join("\n\n")))
+        }
+
+        #[test]
+        fn ranges_shift_what_they_cover() {
+            check(
+                "AlmanacRange::get_destination shifts covered sources only",
+                |source| (almanac_range(source), source.u64_in(0..=130)),
+                |(range, value)| match range.get_destination(*value) {
+                    Some(destination) => {
+                        assert!((range.source..range.source + range.range).contains(value));
+                        assert_eq!(destination - range.destination, value - range.source);
+                    }
+                    None => assert!(!(range.source..range.source + range.range).contains(value)),
+                },
+            );
+        }
+
+        #[test]
+        fn maps_are_bijections_on_covered_ranges() {
+            check("AlmanacMap::get_destination is a bijection on covered ranges", |source| almanac_map(source, "a-to-b"), |map| {
+                let covered: Vec<u64> = map.ranges.iter().flat_map(|range| range.source..range.source + range.range).collect();
+                let images: BTreeSet<u64> = covered.iter().map(|value| map.get_destination(*value)).collect();
+                let destinations: BTreeSet<u64> = map.ranges.iter().flat_map(|range| range.destination..range.destination + range.range).collect();
+
+                assert_eq!(images.len(), covered.len(), "two sources share a destination");
+                assert_eq!(images, destinations);
+
+                let end = covered.iter().max().map_or(0, |max| max + 5);
+                for value in (0..end).filter(|value| !covered.contains(value)) {
+                    assert_eq!(map.get_destination(value), value);
+                }
+            });
+        }
+
+        #[test]
+        fn seed_ranges_cover_every_seed() {
+            check("part two is part one over every seed of the ranges", almanac, |(ranges, maps)| {
+                let pairs: Vec<u64> = ranges.iter().flat_map(|&(start, length)| [start, length]).collect();
+                let almanac = parse_almanac(&pairs, maps);
+                let lowest = ranges
+                    .iter()
+                    .flat_map(|&(start, length)| start..start + length)
+                    .map(|seed| almanac.get_seed_location_number(seed))
+                    .min();
+
+                assert_eq!(almanac.get_lowest_location_number_from_ranges().ok(), lowest);
+            });
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
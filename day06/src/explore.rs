@@ -0,0 +1,41 @@
+//! What `aoc repl` shows about the race document.
+
+use aoc_common::{get_item, Explorer};
+
+use crate::{Day06, Document};
+
+pub const EXPLORER: Explorer<Day06> = Explorer {
+    usage: "query <race | joined>   the ways to win one race, or the joined race of part two",
+    inspect,
+    query,
+};
+
+fn inspect(document: &Document) -> String {
+    match document.get_joined_race() {
+        Some(race) => format!(
+            "{} race(s), or one race of {}ms with a record of {}mm",
+            document.get_races().len(),
+            race.get_time(),
+            race.get_distance()
+        ),
+        None => format!("{} race(s), too long to join into one race", document.get_races().len()),
+    }
+}
+
+fn query(document: &Document, arguments: &[&str]) -> Result<String, String> {
+    let joined;
+    let race = match arguments.first() {
+        Some(&"joined") => {
+            joined = document.get_joined_race().ok_or("the joined race does not fit in 64 bits")?;
+            &joined
+        }
+        _ => get_item(document.get_races(), arguments, "race")?,
+    };
+
+    Ok(format!(
+        "{}ms with a record of {}mm, {} way(s) to win",
+        race.get_time(),
+        race.get_distance(),
+        race.get_margin_of_error()
+    ))
+}
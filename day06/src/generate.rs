@@ -0,0 +1,66 @@
+//! Race documents for `aoc generate`, each race's ways to win found by bisection rather than
+//! by the solver's square root.
+
+use aoc_common::{get_answers, get_between, get_size, Args, Generated, Generator, Rng};
+
+pub const GENERATOR: Generator = Generator {
+    options: "--races <n> --max-time <n>",
+    generate,
+};
+
+/// The number of whole charging times that beat `distance`, found by bisection.
+fn get_ways_to_win(time: u64, distance: u64) -> u64 {
+    let beats = |charging: u64| u128::from(charging) * u128::from(time - charging) > u128::from(distance);
+    let (mut low, mut high) = (0, time / 2);
+
+    if !beats(high) {
+        return 0;
+    }
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if beats(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    time - 2 * low + 1
+}
+
+/// Races whose records can always be beaten, with times up to `--max-time`, which may be as
+/// large as a `u64`. Part two has no answer once the joined digits no longer fit in one.
+fn generate(rng: &mut Rng, args: &mut Args) -> Result<Generated, String> {
+    let races = get_size(args, "--races", 4, 1)?;
+    let max_time = get_size(args, "--max-time", 100, 2)?;
+
+    let mut times = vec![];
+    let mut distances = vec![];
+
+    for _ in 0..races {
+        let time = get_between(rng, max_time / 2 + 1, max_time);
+        // Holding for `shortest` only just beats the record, which keeps it within a u64.
+        let shortest = get_between(rng, 1, (time / 2).min(u64::MAX / time));
+
+        times.push(time);
+        distances.push(shortest * (time - shortest) - 1);
+    }
+
+    let join = |numbers: &[u64]| numbers.iter().map(u64::to_string).collect::<String>().parse::<u64>().ok();
+    let part_two = join(&times).zip(join(&distances)).map(|(time, distance)| get_ways_to_win(time, distance));
+
+    let width = times.iter().chain(&distances).map(|number| number.to_string().len()).max().unwrap_or(1);
+    let column = |numbers: &[u64]| numbers.iter().map(|number| format!("  {number:>width$}")).collect::<String>();
+    let input = format!("Time:    {}\nDistance:{}\n", column(&times), column(&distances));
+
+    let part_one = times
+        .iter()
+        .zip(&distances)
+        .try_fold(1u64, |product, (&time, &distance)| product.checked_mul(get_ways_to_win(time, distance)));
+
+    Ok(Generated {
+        input,
+        answers: get_answers(part_one, part_two),
+    })
+}
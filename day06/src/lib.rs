@@ -0,0 +1,315 @@
+//! Day 6: Wait For It
+//!
+//! Boat races won by holding the button long enough, but not too long.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::{parse_number, Answer, Explorer, Generator, ParseError, Solution};
+
+mod explore;
+mod generate;
+
+/// A single race: one column of the document, or all of them joined for part two.
+#[derive(Debug)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    /// How long the race lasts, in milliseconds.
+    pub fn get_time(&self) -> u64 {
+        self.time
+    }
+
+    /// The record distance to beat, in millimetres.
+    pub fn get_distance(&self) -> u64 {
+        self.distance
+    }
+
+    /// Part two: the number of ways to beat the record distance.
+    ///
+    /// The distance travelled is a downward parabola in the charging time, peaking at half the
+    /// time, so the winning times are one run starting at its lower root. The products are taken
+    /// in a u128, which races as long as a u64 allows still fit in.
+    pub fn get_margin_of_error(&self) -> u64 {
+        let (time, distance) = (u128::from(self.time), u128::from(self.distance));
+        let beats = |time_charging: u128| time_charging * (time - time_charging) > distance;
+
+        if !beats(time / 2) {
+            return 0;
+        }
+
+        // The integer square root rounds down, so the root is only close; nudge it onto the
+        // first winning time.
+        let mut first = (time - (time * time - 4 * distance).isqrt()) / 2;
+        while !beats(first) {
+            first += 1;
+        }
+        while first > 0 && beats(first - 1) {
+            first -= 1;
+        }
+
+        (time - 2 * first + 1) as u64
+    }
+}
+
+/// The races listed in the `Time:` and `Distance:` columns.
+pub struct Document {
+    races: Vec<Race>,
+}
+
+fn get_lines(value: &str) -> Result<(&str, &str), ParseError> {
+    if value.trim().is_empty() {
+        return Err(ParseError::empty_input(value, "expected `Time:` and `Distance:` lines"));
+    }
+
+    let mut lines = value.lines();
+
+    let times_text = lines.next().unwrap_or(value);
+    if !times_text.starts_with("Time:") {
+        return Err(ParseError::new(times_text, "expected `Time: <numbers>`"));
+    }
+
+    let distances_text = lines
+        .next()
+        .ok_or_else(|| ParseError::after(times_text, "expected a `Distance: <numbers>` line after this one"))?;
+    if !distances_text.starts_with("Distance:") {
+        return Err(ParseError::new(distances_text, "expected `Distance: <numbers>`"));
+    }
+
+    Ok((times_text, distances_text))
+}
+
+/// The numbers written one after another, as `12`, `3` read `123`, with checked arithmetic.
+fn join_digits(mut numbers: impl Iterator<Item = u64>) -> Option<u64> {
+    numbers.try_fold(0_u64, |joined, number| {
+        // A 20 digit number shifts by more than a u64 holds, even onto nothing joined yet.
+        let shift = 10_u128.pow(number.checked_ilog10().unwrap_or(0) + 1);
+        let joined = u128::from(joined).checked_mul(shift)? + u128::from(number);
+
+        u64::try_from(joined).ok()
+    })
+}
+
+fn extract_numbers(value: &str) -> Result<Vec<u64>, ParseError> {
+    let (_text, numbers) = value.split_once(':').unwrap_or((value, ""));
+
+    numbers
+        .split_whitespace()
+        .map(parse_number::<u64>)
+        .collect()
+}
+
+impl FromStr for Document {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (times_text, distances_text) = get_lines(value)?;
+
+        let times = extract_numbers(times_text)?;
+        let distances = extract_numbers(distances_text)?;
+
+        if times.len() != distances.len() {
+            return Err(ParseError::new(
+                distances_text,
+                format!("expected {} distances, one for each time, found {}", times.len(), distances.len()),
+            ));
+        }
+
+        let races = times.iter().zip(distances.iter())
+            .map(|(time, distance)| Race {
+                time: *time,
+                distance: *distance,
+            }).collect();
+
+        Ok(Document {
+            races,
+        })
+    }
+}
+
+impl Document {
+    /// The races, in the order of the columns.
+    pub fn get_races(&self) -> &[Race] {
+        &self.races
+    }
+
+    /// Part one: the number of ways to win each race, multiplied together, or `None` if the
+    /// product overflows a `u64`.
+    pub fn get_total_margin_of_error(&self) -> Option<u64> {
+        self.races.iter().try_fold(1_u64, |product, race| product.checked_mul(race.get_margin_of_error()))
+    }
+
+    /// Part two: the columns read as one race, with the spaces between digits ignored, or `None`
+    /// if the joined time or distance overflows a `u64`.
+    pub fn get_joined_race(&self) -> Option<Race> {
+        Some(Race {
+            time: join_digits(self.races.iter().map(Race::get_time))?,
+            distance: join_digits(self.races.iter().map(Race::get_distance))?,
+        })
+    }
+}
+
+/// The [`Solution`] for day 6, parsing the input as a [`Document`] of races.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    type Input = Document;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let document: Document = input.parse()?;
+        aoc_common::debug!("{} races", document.races.len());
+
+        Ok(document)
+    }
+
+    fn part_one(input: &Self::Input) -> Answer {
+        input.get_total_margin_of_error().into()
+    }
+
+    fn part_two(input: &Self::Input) -> Answer {
+        input.get_joined_race().map(|race| race.get_margin_of_error()).into()
+    }
+
+    fn get_explorer() -> Option<Explorer<Self>> {
+        Some(explore::EXPLORER)
+    }
+
+    fn get_generator() -> Option<Generator> {
+        Some(generate::GENERATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_input() {
+        let text = "Time:      7  15   30\nDistance:  9  40";
+
+        let error = Day06::parse(text).err().unwrap().locate(text);
+
+        assert_eq!(error.get_message(), "expected 3 distances, one for each time, found 2");
+        assert_eq!(error.get_location().map(|location| location.line), Some(2));
+        assert!(!Day06::parse("Time: 7").err().unwrap().is_empty_input());
+        assert!(Day06::parse("").err().unwrap().is_empty_input());
+        assert!(Day06::parse("Time: 7\nDistance: 1a").is_err());
+    }
+
+    #[test]
+    fn large_races() {
+        let overflow = Answer::Unsolved(aoc_common::OVERFLOW_REASON.to_string());
+
+        // Each race fits, but joined they do not, and neither does the product of their ways to win.
+        let document = Day06::parse("Time: 18446744073709551615 18446744073709551615\nDistance: 1 1").unwrap();
+        assert_eq!(document.get_races()[0].get_margin_of_error(), 18446744073709551614);
+        assert!(document.get_joined_race().is_none());
+        assert_eq!(Day06::part_one(&document), overflow);
+        assert_eq!(Day06::part_two(&document), overflow);
+
+        let document = Day06::parse("Time: 4294967296 10\nDistance: 0 0").unwrap();
+        assert_eq!(Day06::part_one(&document), Answer::Unsigned(4294967295 * 9));
+        assert_eq!(document.get_joined_race().map(|race| race.get_time()), Some(429496729610));
+
+        let document = Day06::parse("Time: 18446744073709551615\nDistance: 1").unwrap();
+        assert_eq!(Day06::part_two(&document), Answer::Unsigned(18446744073709551614));
+    }
+
+    mod examples {
+        include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+    }
+
+    mod fuzz {
+        include!(concat!(env!("OUT_DIR"), "/fuzz_tests.rs"));
+    }
+
+    mod properties {
+        use super::*;
+        use aoc_common::{check, Source};
+
+        /// A race with a record somewhere from trivially beaten to unbeatable.
+        fn race(source: &mut Source) -> Race {
+            let time = source.u64_in(0..=2000);
+
+            Race {
+                time,
+                distance: source.u64_in(0..=time * time / 4 + 2),
+            }
+        }
+
+        /// The first charging time that beats the record, by bisection over the rising half.
+        fn get_first_winning_time(race: &Race) -> Option<u64> {
+            let beats = |charging: u64| charging * (race.time - charging) > race.distance;
+            let (mut low, mut high) = (0, race.time / 2);
+
+            if !beats(high) {
+                return None;
+            }
+
+            while low < high {
+                let middle = (low + high) / 2;
+                if beats(middle) {
+                    high = middle;
+                } else {
+                    low = middle + 1;
+                }
+            }
+
+            Some(low)
+        }
+
+        #[test]
+        fn winning_times_are_symmetric() {
+            check("the winning charging times are one run, symmetric around half the time", race, |race| {
+                let expected = get_first_winning_time(race).map_or(0, |first| race.time - 2 * first + 1);
+
+                assert_eq!(race.get_margin_of_error(), expected);
+            });
+        }
+
+        #[test]
+        fn longer_records_are_harder() {
+            check("raising the record never adds ways to win", race, |race| {
+                let harder = Race {
+                    time: race.time,
+                    distance: race.distance + 1,
+                };
+
+                assert!(harder.get_margin_of_error() <= race.get_margin_of_error());
+            });
+        }
+
+        #[test]
+        fn races_join_their_digits() {
+            check(
+                "part two reads the columns as one race",
+                |source| source.vec_of(1..=3, |source| (source.u64_in(0..=999), source.u64_in(0..=999), source.usize_in(1..=4))),
+                |columns| {
+                    let times: String = columns.iter().map(|(time, _, padding)| format!("{}{time}", " ".repeat(*padding))).collect();
+                    let distances: String = columns.iter().map(|(_, distance, padding)| format!("{}{distance}", " ".repeat(*padding))).collect();
+                    let text = format!("Time:{times}\nDistance:{distances}");
+
+                    let document = Day06::parse(&text).unwrap();
+                    let race = document.get_joined_race().unwrap();
+                    let joined = |numbers: String| numbers.replace(' ', "").parse::<u64>().unwrap();
+
+                    assert_eq!((race.time, race.distance), (joined(times), joined(distances)));
+                    assert_eq!(document.races.len(), columns.len());
+                    assert_eq!(
+                        document.get_total_margin_of_error(),
+                        Some(document.races.iter().map(Race::get_margin_of_error).product::<u64>())
+                    );
+                },
+            );
+        }
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}